@@ -0,0 +1,134 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::Entry;
+use anyhow::{ensure, Result};
+use serde::Serialize;
+use std::time::Duration;
+
+/// The result of a single run, with the time spent in each phase
+#[derive(Debug)]
+pub struct Timed {
+    pub parse: Duration,
+    pub solve: Duration,
+    pub answer: String,
+}
+
+impl Timed {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+// Stats //
+
+/// Statistics of several runs of a solver; all durations are in
+/// nanoseconds so that they can be exported as they are
+#[derive(Serialize, Debug)]
+pub struct Stats {
+    pub solver: String,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+    pub parse_median_ns: u64,
+    pub solve_median_ns: u64,
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos() as u64
+}
+
+/// Median of the given durations; sorts them in the process
+pub fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort_unstable();
+    let mid = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        (durations[mid - 1] + durations[mid]) / 2
+    } else {
+        durations[mid]
+    }
+}
+
+impl Stats {
+    pub fn new(solver: String, timings: &[Timed]) -> Stats {
+        let mut totals = timings.iter().map(Timed::total).collect::<Vec<_>>();
+        let mut parses = timings.iter().map(|t| t.parse).collect::<Vec<_>>();
+        let mut solves = timings.iter().map(|t| t.solve).collect::<Vec<_>>();
+        Stats {
+            solver,
+            runs: timings.len(),
+            min_ns: nanos(*totals.iter().min().unwrap()),
+            max_ns: nanos(*totals.iter().max().unwrap()),
+            median_ns: nanos(median(&mut totals)),
+            parse_median_ns: nanos(median(&mut parses)),
+            solve_median_ns: nanos(median(&mut solves)),
+        }
+    }
+}
+
+// Benchmarking //
+
+/// Run the solver `runs` times over the input, which is kept in memory so
+/// that reading it is not measured
+pub fn bench(entry: &Entry, input: &[u8], runs: usize) -> Result<Stats> {
+    ensure!(runs > 0, "the number of runs must be positive");
+    let mut timings = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let mut bufin = input;
+        timings.push((entry.timed)(&mut bufin)?);
+    }
+    Ok(Stats::new(entry.name(), &timings))
+}
+
+fn fmt_ns(ns: u64) -> String {
+    format!("{:.3?}", Duration::from_nanos(ns))
+}
+
+pub fn print_table(stats: &[Stats]) {
+    println!(
+        "{:<7} {:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "solver", "runs", "min", "median", "max", "parse", "solve"
+    );
+    for s in stats {
+        println!(
+            "{:<7} {:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            s.solver,
+            s.runs,
+            fmt_ns(s.min_ns),
+            fmt_ns(s.median_ns),
+            fmt_ns(s.max_ns),
+            fmt_ns(s.parse_median_ns),
+            fmt_ns(s.solve_median_ns)
+        );
+    }
+}
+
+pub fn to_json(stats: &[Stats]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(stats)?)
+}
+
+#[test]
+fn test_median() {
+    let ms = Duration::from_millis;
+    assert_eq!(median(&mut [ms(3), ms(1), ms(2)]), ms(2));
+    assert_eq!(
+        median(&mut [ms(4), ms(1), ms(2), ms(3)]),
+        Duration::from_micros(2500)
+    );
+}
+
+#[test]
+fn test_bench() -> Result<()> {
+    let entry = crate::find("1a")?;
+    let stats = bench(entry, b"1721\n979\n366\n299\n675\n1456\n", 3)?;
+    assert_eq!(stats.solver, "day1a");
+    assert_eq!(stats.runs, 3);
+    assert!(stats.min_ns <= stats.median_ns && stats.median_ns <= stats.max_ns);
+    let json = to_json(&[stats])?;
+    assert!(json.contains("\"solver\": \"day1a\""));
+    assert!(json.contains("\"parse_median_ns\""));
+    assert!(bench(entry, b"", 0).is_err());
+    Ok(())
+}
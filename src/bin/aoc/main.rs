@@ -0,0 +1,428 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use adventofcode2020::*;
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs::File;
+use std::io::{stdin, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+mod bench;
+mod verify;
+
+use bench::Timed;
+
+// Registry //
+
+type Runner = fn(&mut dyn BufRead) -> Result<Timed>;
+
+struct Entry {
+    day: u32,
+    part: char,
+    timed: Runner,
+}
+
+impl Entry {
+    fn run(&self, bufin: &mut dyn BufRead) -> Result<String> {
+        Ok((self.timed)(bufin)?.answer)
+    }
+
+    fn name(&self) -> String {
+        format!("day{}{}", self.day, self.part)
+    }
+
+    fn input_path(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("day{}.txt", self.day))
+    }
+}
+
+macro_rules! solver {
+    ($day:expr, $part:expr, $solver:ty, $func:ident) => {
+        Entry {
+            day: $day,
+            part: $part,
+            timed: |bufin| {
+                let start = Instant::now();
+                let input = <$solver>::parse(bufin)?;
+                let parse = start.elapsed();
+                let start = Instant::now();
+                let answer = <$solver>::$func(&input)?.to_string();
+                Ok(Timed {
+                    parse,
+                    solve: start.elapsed(),
+                    answer,
+                })
+            },
+        }
+    };
+}
+
+const SOLVERS: &[Entry] = &[
+    solver!(1, 'a', day01::Day01, part1),
+    solver!(1, 'b', day01::Day01, part2),
+    solver!(2, 'a', day02::Day02, part1),
+    solver!(2, 'b', day02::Day02, part2),
+    solver!(3, 'a', day03::Day03, part1),
+    solver!(3, 'b', day03::Day03, part2),
+    solver!(4, 'a', day04::Day04, part1),
+    solver!(4, 'b', day04::Day04, part2),
+    solver!(5, 'a', day05::Day05, part1),
+    solver!(5, 'b', day05::Day05, part2),
+    solver!(6, 'a', day06::Day06, part1),
+    solver!(6, 'b', day06::Day06, part2),
+    solver!(7, 'a', day07::Day07, part1),
+    solver!(7, 'b', day07::Day07, part2),
+    solver!(8, 'a', day08::Day08, part1),
+    solver!(8, 'b', day08::Day08, part2),
+    solver!(9, 'a', day09::Day09, part1),
+    solver!(9, 'b', day09::Day09, part2),
+    solver!(10, 'a', day10::Day10, part1),
+    solver!(10, 'b', day10::Day10, part2),
+    solver!(11, 'a', day11::Day11, part1),
+    solver!(11, 'b', day11::Day11, part2),
+    solver!(12, 'a', day12::Day12, part1),
+    solver!(12, 'b', day12::Day12, part2),
+    solver!(13, 'a', day13::Day13, part1),
+    solver!(13, 'b', day13::Day13, part2),
+    solver!(14, 'a', day14::Day14, part1),
+    solver!(14, 'b', day14::Day14, part2),
+    solver!(15, 'a', day15::Day15, part1),
+    solver!(15, 'b', day15::Day15, part2),
+    solver!(16, 'a', day16::Day16, part1),
+    solver!(16, 'b', day16::Day16, part2),
+    solver!(17, 'a', day17::Day17, part1),
+    solver!(17, 'b', day17::Day17, part2),
+    solver!(18, 'a', day18::Day18, part1),
+    solver!(18, 'b', day18::Day18, part2),
+    solver!(19, 'a', day19::Day19, part1),
+    solver!(19, 'b', day19::Day19, part2),
+    solver!(20, 'a', day20::Day20, part1),
+    solver!(20, 'b', day20::Day20, part2),
+    solver!(21, 'a', day21::Day21, part1),
+    solver!(21, 'b', day21::Day21, part2),
+    solver!(22, 'a', day22::Day22, part1),
+    solver!(22, 'b', day22::Day22, part2),
+    solver!(23, 'a', day23::Day23, part1),
+    solver!(23, 'b', day23::Day23, part2),
+    solver!(24, 'a', day24::Day24, part1),
+    solver!(24, 'b', day24::Day24, part2),
+    solver!(25, 'a', day25::Day25, part1),
+];
+
+/// Finds a solver by name, accepting both "8b" and "day8b"
+fn find(name: &str) -> Result<&'static Entry> {
+    let short = name.strip_prefix("day").unwrap_or(name);
+    SOLVERS
+        .iter()
+        .find(|s| s.name()[3..] == *short)
+        .ok_or_else(|| anyhow!("unknown solver \"{}\", see \"aoc list\"", name))
+}
+
+/// The solvers picked in the command line, either by name or with --all
+fn select(all: bool, names: Vec<&str>) -> Result<Vec<&'static Entry>> {
+    if all {
+        if !names.is_empty() {
+            bail!("--all can't be combined with solver names");
+        }
+        Ok(SOLVERS.iter().collect())
+    } else {
+        names.into_iter().map(find).collect()
+    }
+}
+
+// Commands //
+
+fn cmd_list() -> Result<()> {
+    for solver in SOLVERS {
+        println!("{}", solver.name());
+    }
+    Ok(())
+}
+
+fn cmd_run(args: &[String]) -> Result<()> {
+    let mut all = false;
+    let mut inputs: Option<PathBuf> = None;
+    let mut names = vec![];
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--inputs" => {
+                let dir = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--inputs requires a directory"))?;
+                inputs = Some(PathBuf::from(dir));
+            }
+            _ if arg.starts_with('-') => bail!("unknown option {}", arg),
+            _ => names.push(arg.as_str()),
+        }
+    }
+    let solvers = select(all, names)?;
+    match (inputs, solvers.as_slice()) {
+        (_, []) => bail!("no solver selected, use a name or --all"),
+        (None, [solver]) => {
+            println!("{}", solver.run(&mut stdin().lock())?);
+        }
+        (None, _) => bail!("running more than one solver requires --inputs"),
+        (Some(dir), solvers) => {
+            for solver in solvers {
+                let path = solver.input_path(&dir);
+                if !path.exists() {
+                    println!("{}: no input at {}", solver.name(), path.display());
+                    continue;
+                }
+                let mut bufin = BufReader::new(File::open(&path)?);
+                println!("{}: {}", solver.name(), solver.run(&mut bufin)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_bench(args: &[String]) -> Result<()> {
+    let mut all = false;
+    let mut inputs: Option<PathBuf> = None;
+    let mut runs = 10;
+    let mut json = false;
+    let mut names = vec![];
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--json" => json = true,
+            "--inputs" => {
+                let dir = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--inputs requires a directory"))?;
+                inputs = Some(PathBuf::from(dir));
+            }
+            "--runs" => {
+                runs = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--runs requires a number"))?
+                    .parse()
+                    .context("invalid number of runs")?;
+            }
+            _ if arg.starts_with('-') => bail!("unknown option {}", arg),
+            _ => names.push(arg.as_str()),
+        }
+    }
+    let solvers = select(all, names)?;
+    let mut stats = vec![];
+    match (inputs, solvers.as_slice()) {
+        (_, []) => bail!("no solver selected, use a name or --all"),
+        (None, [solver]) => {
+            let mut input = vec![];
+            stdin().lock().read_to_end(&mut input)?;
+            stats.push(bench::bench(solver, &input, runs)?);
+        }
+        (None, _) => bail!("benchmarking more than one solver requires --inputs"),
+        (Some(dir), solvers) => {
+            for solver in solvers {
+                let path = solver.input_path(&dir);
+                if !path.exists() {
+                    eprintln!("{}: no input at {}", solver.name(), path.display());
+                    continue;
+                }
+                let input = std::fs::read(&path)?;
+                stats.push(bench::bench(solver, &input, runs)?);
+            }
+        }
+    }
+    if json {
+        println!("{}", bench::to_json(&stats)?);
+    } else {
+        bench::print_table(&stats);
+    }
+    Ok(())
+}
+
+fn cmd_verify(args: &[String]) -> Result<()> {
+    let mut inputs: Option<PathBuf> = None;
+    let mut answers_path: Option<PathBuf> = None;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--inputs" => {
+                let dir = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--inputs requires a directory"))?;
+                inputs = Some(PathBuf::from(dir));
+            }
+            "--answers" => {
+                let path = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--answers requires a file"))?;
+                answers_path = Some(PathBuf::from(path));
+            }
+            _ => bail!("unknown argument {}", arg),
+        }
+    }
+    let dir = inputs.ok_or_else(|| anyhow!("verify requires --inputs"))?;
+    let answers_path = answers_path.unwrap_or_else(|| dir.join("answers.toml"));
+    let contents = std::fs::read_to_string(&answers_path)
+        .with_context(|| format!("error reading {}", answers_path.display()))?;
+    let answers = verify::parse_answers(&contents)
+        .with_context(|| format!("error parsing {}", answers_path.display()))?;
+    let failed = verify::verify(SOLVERS, &dir, &answers);
+    if failed > 0 {
+        bail!("{} solver(s) failed verification", failed);
+    }
+    Ok(())
+}
+
+fn cmd_vm(args: &[String]) -> Result<()> {
+    let path = match args {
+        [path] => path,
+        _ => bail!("vm requires a program file"),
+    };
+    let file = File::open(path).with_context(|| format!("error opening {}", path))?;
+    let table = vm::OpTable::default();
+    let program = vm::parse_program(8, BufReader::new(file), &table)?;
+    let mut machine = vm::Vm::new(table, program)?;
+    vm::repl(&mut machine, stdin().lock(), std::io::stdout())
+}
+
+fn cmd_bags(args: &[String]) -> Result<()> {
+    let path = match args {
+        [path] => path,
+        _ => bail!("bags requires a rules file"),
+    };
+    let file = File::open(path).with_context(|| format!("error opening {}", path))?;
+    let graph = day07::Day07::parse(BufReader::new(file))?;
+    graph.toposort()?;
+    print!("{}", graph.to_dot());
+    Ok(())
+}
+
+fn cmd_passports(args: &[String]) -> Result<()> {
+    let mut schema = day04::Schema::default();
+    let mut path = None;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--schema" => {
+                let file = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--schema requires a file"))?;
+                schema = day04::Schema::load(Path::new(file))?;
+            }
+            _ if arg.starts_with('-') => bail!("unknown option {}", arg),
+            _ if path.is_none() => path = Some(arg),
+            _ => bail!("unexpected argument {}", arg),
+        }
+    }
+    let path = path.ok_or_else(|| anyhow!("passports requires an input file"))?;
+    let file = File::open(path).with_context(|| format!("error opening {}", path))?;
+    let passports = day04::Day04::parse(BufReader::new(file))?;
+    print!("{}", schema.report(&passports));
+    Ok(())
+}
+
+// Logging //
+
+/// Expand the per-day targets in a log filter spec, so that "day3=debug"
+/// means "adventofcode2020::day03=debug"
+fn log_filters(spec: &str) -> String {
+    spec.split(',')
+        .map(|directive| {
+            let (target, level) = directive
+                .split_once('=')
+                .map_or((directive, None), |(t, l)| (t, Some(l)));
+            let target = match target.strip_prefix("day").map(str::parse::<u32>) {
+                Some(Ok(day)) => format!("adventofcode2020::day{:02}", day),
+                _ => target.to_string(),
+            };
+            match level {
+                Some(level) => format!("{}={}", target, level),
+                None => target,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Logging is off unless enabled with -v (info), -vv (debug) or -vvv
+/// (trace), or with a filter in the AOC_LOG environment variable
+fn init_logging(verbosity: usize) {
+    let level = match verbosity {
+        0 => log::LevelFilter::Off,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    let mut builder = env_logger::Builder::new();
+    builder.filter_module("adventofcode2020", level);
+    if let Ok(spec) = std::env::var("AOC_LOG") {
+        builder.parse_filters(&log_filters(&spec));
+    }
+    builder.format_timestamp(None).init();
+}
+
+/// Remove the verbosity options from the arguments, returning the level
+fn take_verbosity(args: &mut Vec<String>) -> usize {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        let vs = match arg.as_str() {
+            "--verbose" => 1,
+            _ => match arg.strip_prefix('-') {
+                Some(v) if !v.is_empty() && v.chars().all(|c| c == 'v') => v.len(),
+                _ => return true,
+            },
+        };
+        verbosity += vs;
+        false
+    });
+    verbosity
+}
+
+fn usage() -> &'static str {
+    "usage: aoc [-v|-vv|-vvv] <command>\n       aoc list\n       aoc run <day><part> [--inputs <dir>]\n       aoc run --all --inputs <dir>\n       aoc bench [--runs <n>] [--json] (<day><part>... | --all) [--inputs <dir>]\n       aoc verify --inputs <dir> [--answers <file>]\n       aoc vm <program>\n       aoc bags <rules>\n       aoc passports [--schema <file>] <input>"
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    init_logging(take_verbosity(&mut args));
+    match args.first().map(|s| s.as_str()) {
+        Some("list") => cmd_list(),
+        Some("run") => cmd_run(&args[1..]),
+        Some("bench") => cmd_bench(&args[1..]),
+        Some("verify") => cmd_verify(&args[1..]),
+        Some("vm") => cmd_vm(&args[1..]),
+        Some("bags") => cmd_bags(&args[1..]),
+        Some("passports") => cmd_passports(&args[1..]),
+        _ => Err(anyhow!("{}", usage())),
+    }
+}
+
+#[test]
+fn test_find() -> Result<()> {
+    assert_eq!(find("8b")?.name(), "day8b");
+    assert_eq!(find("day25a")?.name(), "day25a");
+    assert!(find("25b").is_err());
+    assert!(find("day").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_logging_args() {
+    let mut args = ["-vv", "run", "--verbose", "16b", "-v"]
+        .map(String::from)
+        .to_vec();
+    assert_eq!(take_verbosity(&mut args), 4);
+    assert_eq!(args, vec!["run", "16b"]);
+    assert_eq!(
+        log_filters("day3=debug,day16,info"),
+        "adventofcode2020::day03=debug,adventofcode2020::day16,info"
+    );
+}
+
+#[test]
+fn test_registry() {
+    assert_eq!(SOLVERS.len(), 49);
+    for (i, solver) in SOLVERS.iter().enumerate() {
+        assert_eq!(solver.day, i as u32 / 2 + 1);
+    }
+}
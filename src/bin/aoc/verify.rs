@@ -0,0 +1,154 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::{find, Entry};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+// answers.toml //
+
+/// An expected answer; numbers can be written without quotes
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Expected {
+    Num(i64),
+    Str(String),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Num(n) => write!(f, "{}", n),
+            Expected::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Answer {
+    pub day: u32,
+    pub part: char,
+    pub expected: Expected,
+}
+
+#[derive(Deserialize, Debug)]
+struct AnswersFile {
+    #[serde(default)]
+    answer: Vec<Answer>,
+}
+
+/// Parse the contents of an answers file, indexing the answers by solver
+/// name
+pub fn parse_answers(contents: &str) -> Result<BTreeMap<String, Expected>> {
+    let file: AnswersFile = toml::from_str(contents)?;
+    let mut answers = BTreeMap::new();
+    for answer in file.answer {
+        let entry = find(&format!("{}{}", answer.day, answer.part))?;
+        if answers.insert(entry.name(), answer.expected).is_some() {
+            return Err(anyhow!("duplicate answer for {}", entry.name()));
+        }
+    }
+    Ok(answers)
+}
+
+// Checking //
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+/// Run the solver on its input in dir, if there is one, and compare the
+/// result with the expected answer; returns the status and what we got
+pub fn check(entry: &Entry, dir: &Path, expected: Option<&Expected>) -> (Status, String) {
+    let path = entry.input_path(dir);
+    if !path.exists() {
+        return (Status::Missing, "(no input)".to_string());
+    }
+    let result = File::open(&path)
+        .map_err(|e| anyhow!(e))
+        .and_then(|file| entry.run(&mut BufReader::new(file)));
+    match (result, expected) {
+        (Err(e), _) => (Status::Fail, format!("error: {}", e)),
+        (Ok(got), None) => (Status::Missing, got),
+        (Ok(got), Some(expected)) if got == expected.to_string() => (Status::Pass, got),
+        (Ok(got), Some(_)) => (Status::Fail, got),
+    }
+}
+
+/// Check all solvers against the answers, printing a table; returns the
+/// number of failures
+pub fn verify(entries: &[Entry], dir: &Path, answers: &BTreeMap<String, Expected>) -> usize {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    println!("{:<7} {:<7} {:<20} got", "solver", "status", "expected");
+    for entry in entries {
+        let expected = answers.get(&entry.name());
+        let (status, got) = check(entry, dir, expected);
+        let expected_str = expected.map_or_else(|| "-".to_string(), |e| e.to_string());
+        println!(
+            "{:<7} {:<7} {:<20} {}",
+            entry.name(),
+            status,
+            expected_str,
+            got
+        );
+        match status {
+            Status::Pass => passed += 1,
+            Status::Fail => failed += 1,
+            Status::Missing => missing += 1,
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed
+}
+
+#[test]
+fn test_parse_answers() -> Result<()> {
+    let answers = parse_answers(
+        "[[answer]]\nday = 1\npart = \"a\"\nexpected = 514579\n\n[[answer]]\nday = 21\npart = \"b\"\nexpected = \"mxmxvkd,sqjhc,fvjkl\"\n",
+    )?;
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers["day1a"].to_string(), "514579");
+    assert_eq!(answers["day21b"].to_string(), "mxmxvkd,sqjhc,fvjkl");
+    assert!(parse_answers("[[answer]]\nday = 25\npart = \"b\"\nexpected = 1\n").is_err());
+    assert!(parse_answers(
+        "[[answer]]\nday = 1\npart = \"a\"\nexpected = 1\n[[answer]]\nday = 1\npart = \"a\"\nexpected = 2\n"
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_check() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("day1.txt"), "1721\n979\n366\n299\n675\n1456\n")?;
+    let entry = find("1a")?;
+    assert_eq!(
+        check(entry, &dir, Some(&Expected::Num(514579))),
+        (Status::Pass, "514579".to_string())
+    );
+    assert_eq!(check(entry, &dir, Some(&Expected::Num(1))).0, Status::Fail);
+    assert_eq!(check(entry, &dir, None).0, Status::Missing);
+    assert_eq!(check(find("2a")?, &dir, None).0, Status::Missing);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
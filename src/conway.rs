@@ -0,0 +1,236 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::{anyhow, ensure, Result};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::ops;
+use std::str::FromStr;
+
+// Point //
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<const N: usize>(pub [i32; N]);
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Point([0; N])
+    }
+}
+
+impl<const N: usize> Point<N> {
+    /// Point in the plane of the first two coordinates; None if there are
+    /// fewer than 2 dimensions
+    pub fn from_xy(x: i32, y: i32) -> Option<Point<N>> {
+        if N < 2 {
+            return None;
+        }
+        let mut p = Point::default();
+        p.0[0] = x;
+        p.0[1] = y;
+        Some(p)
+    }
+
+    /// All 3^N - 1 offsets to the neighbors of a point
+    pub fn neighbor_offsets() -> Vec<Point<N>> {
+        let mut offsets = vec![Point::default()];
+        for dim in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|p| {
+                    (-1..=1).map(move |d| {
+                        let mut q = p;
+                        q.0[dim] = d;
+                        q
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|p| *p != Point::default());
+        offsets
+    }
+}
+
+impl<const N: usize> ops::Add for Point<N> {
+    type Output = Point<N>;
+    fn add(mut self, other: Point<N>) -> Point<N> {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a += b;
+        }
+        self
+    }
+}
+
+// Rules //
+
+/// Birth/survive rules: an inactive cell becomes active if its number of
+/// active neighbors is in `birth`, and an active cell stays active if it is
+/// in `survive`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub birth: BTreeSet<usize>,
+    pub survive: BTreeSet<usize>,
+}
+
+impl Rules {
+    pub fn new(birth: &[usize], survive: &[usize]) -> Rules {
+        Rules {
+            birth: birth.iter().copied().collect(),
+            survive: survive.iter().copied().collect(),
+        }
+    }
+
+    pub fn next(&self, active: bool, neighbors: usize) -> bool {
+        if active {
+            self.survive.contains(&neighbors)
+        } else {
+            self.birth.contains(&neighbors)
+        }
+    }
+}
+
+/// The rules of the puzzle, which are the ones of Conway's game of life
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules::new(&[3], &[2, 3])
+    }
+}
+
+/// Parse the usual "B3/S23" notation; counts above 9 can be written
+/// separated by commas, as in "B3/S2,3,10"
+impl FromStr for Rules {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>> {
+            let digits = part
+                .strip_prefix(prefix)
+                .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(|| anyhow!("expected {} in rule {:?}", prefix, s))?;
+            if digits.contains(',') {
+                digits.split(',').map(|n| Ok(n.parse::<usize>()?)).collect()
+            } else {
+                digits
+                    .chars()
+                    .map(|c| {
+                        c.to_digit(10)
+                            .map(|d| d as usize)
+                            .ok_or_else(|| anyhow!("invalid count {:?} in rule {:?}", c, s))
+                    })
+                    .collect()
+            }
+        };
+        let (birth, survive) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow!("expected B.../S... rule, got {:?}", s))?;
+        Ok(Rules::new(&counts(birth, 'B')?, &counts(survive, 'S')?))
+    }
+}
+
+// Conway //
+
+/// Set of active cells that evolves according to the rules
+#[derive(Debug, Clone)]
+pub struct Conway<const N: usize> {
+    pub active: BTreeSet<Point<N>>,
+    pub rules: Rules,
+    offsets: Vec<Point<N>>,
+}
+
+impl<const N: usize> Conway<N> {
+    pub fn new(active: impl IntoIterator<Item = Point<N>>, rules: Rules) -> Conway<N> {
+        Conway {
+            active: active.into_iter().collect(),
+            rules,
+            offsets: Point::neighbor_offsets(),
+        }
+    }
+
+    /// Start with the given active cells in the plane of the first two
+    /// coordinates
+    pub fn from_plane(xys: &[(i32, i32)], rules: Rules) -> Result<Conway<N>> {
+        ensure!(N >= 2, "a plane needs at least 2 dimensions, got {}", N);
+        Ok(Conway::new(
+            xys.iter().filter_map(|&(x, y)| Point::from_xy(x, y)),
+            rules,
+        ))
+    }
+
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Run a single cycle
+    pub fn step(&mut self) {
+        let mut neighs = BTreeMap::<Point<N>, usize>::new();
+        for &cell in &self.active {
+            for &offset in &self.offsets {
+                *neighs.entry(cell + offset).or_insert(0) += 1;
+            }
+        }
+        // Active cells without active neighbors are not in neighs
+        let lonely = self
+            .active
+            .iter()
+            .filter(|cell| !neighs.contains_key(cell))
+            .filter(|_| self.rules.survive.contains(&0))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut next = neighs
+            .into_iter()
+            .filter(|(cell, n)| self.rules.next(self.active.contains(cell), *n))
+            .map(|(cell, _)| cell)
+            .collect::<BTreeSet<_>>();
+        next.extend(lonely);
+        self.active = next;
+    }
+
+    /// Run the given number of cycles, returning the number of active cells
+    /// after each one
+    pub fn run(&mut self, cycles: usize) -> Vec<usize> {
+        (0..cycles)
+            .map(|_| {
+                self.step();
+                self.len()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_offsets() {
+    assert_eq!(Point::<2>::neighbor_offsets().len(), 8);
+    assert_eq!(Point::<3>::neighbor_offsets().len(), 26);
+    assert_eq!(Point::<4>::neighbor_offsets().len(), 80);
+}
+
+#[test]
+fn test_rules() -> Result<()> {
+    assert_eq!("B3/S23".parse::<Rules>()?, Rules::default());
+    assert_eq!(
+        "B36/S2,3,12".parse::<Rules>()?,
+        Rules::new(&[3, 6], &[2, 3, 12])
+    );
+    assert!("B3S23".parse::<Rules>().is_err());
+    assert!("B3/X23".parse::<Rules>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_cubes() -> Result<()> {
+    let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+    let mut conway = Conway::<3>::from_plane(&glider, Rules::default())?;
+    assert_eq!(conway.run(3), vec![11, 21, 38]);
+    // A blinker in 2D keeps oscillating
+    let mut conway = Conway::<2>::from_plane(&[(0, 0), (1, 0), (2, 0)], "B3/S23".parse()?)?;
+    assert_eq!(conway.run(4), vec![3, 3, 3, 3]);
+    assert!(conway.active.contains(&Point([1, 0])));
+    assert!(Conway::<1>::from_plane(&glider, Rules::default()).is_err());
+    assert_eq!(Point::<1>::from_xy(1, 2), None);
+    assert_eq!(Point::<3>::from_xy(1, 2), Some(Point([1, 2, 0])));
+    Ok(())
+}
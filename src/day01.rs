@@ -0,0 +1,75 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_line;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+// Solver //
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        bufin
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Ok(parse_line(1, i + 1, &line?)?))
+            .collect::<Result<Vec<_>>>()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        let mut numbers: BTreeSet<i64> = BTreeSet::new();
+        for &n in input {
+            let m = 2020 - n;
+            if numbers.contains(&m) {
+                return Ok(m * n);
+            }
+            numbers.insert(n);
+        }
+        Err(anyhow!("numbers not found"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        let mut numbers: BTreeSet<i64> = BTreeSet::new();
+        for &n in input {
+            for m in &numbers {
+                let k = 2020 - m - n;
+                if numbers.contains(&k) {
+                    return Ok(m * n * k);
+                }
+            }
+            numbers.insert(n);
+        }
+        Err(anyhow!("numbers not found"))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day01::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day01::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"1721\n979\n366\n299\n675\n1456\n";
+    assert_eq!(solve_a(input)?, 514579);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"1721\n979\n366\n299\n675\n1456\n";
+    assert_eq!(solve_b(input)?, 241861950);
+    Ok(())
+}
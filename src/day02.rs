@@ -0,0 +1,111 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::io::BufRead;
+
+fn get<'a>(m: &'a regex::Captures, name: &str) -> Result<&'a str> {
+    Ok(m.name(name)
+        .ok_or_else(|| anyhow!("{} not found", name))?
+        .as_str())
+}
+
+// Entry //
+
+/// A password with its policy: two numbers and a letter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub num1: usize,
+    pub num2: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+// Solver //
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let re =
+            Regex::new(r"^(?P<num1>[0-9]+)-(?P<num2>[0-9]+) (?P<letter>.): (?P<password>.*)$")?;
+        let mut entries = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            let m = re.captures(&line).ok_or_else(|| {
+                ParseError::new(
+                    2,
+                    i + 1,
+                    1,
+                    &line,
+                    "a policy and password, as in \"1-3 a: abcde\"",
+                )
+            })?;
+            entries.push(Entry {
+                num1: get(&m, "num1")?.parse::<usize>()?,
+                num2: get(&m, "num2")?.parse::<usize>()?,
+                letter: get(&m, "letter")?.parse::<char>()?,
+                password: get(&m, "password")?.to_string(),
+            });
+        }
+        Ok(entries)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(input
+            .iter()
+            .filter(|e| {
+                let count = e.password.chars().filter(|&c| c == e.letter).count();
+                e.num1 <= count && count <= e.num2
+            })
+            .count())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(input
+            .iter()
+            .filter(|e| {
+                // zero-based positions
+                let pos1 = e.num1 - 1;
+                let pos2 = e.num2 - 1;
+                let in_pos = e
+                    .password
+                    .chars()
+                    .enumerate()
+                    .filter(|&(pos, c)| c == e.letter && (pos == pos1 || pos == pos2))
+                    .count();
+                in_pos == 1
+            })
+            .count())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day02::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day02::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+    assert_eq!(solve_a(input)?, 2);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+    assert_eq!(solve_b(input)?, 1);
+    Ok(())
+}
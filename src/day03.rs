@@ -0,0 +1,80 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::grid::{Grid, Xy};
+use crate::Solver;
+use anyhow::Result;
+use log::info;
+use std::io::BufRead;
+
+/// Count the trees found going down the map with the given slope; the map
+/// repeats itself to the right
+pub fn count_trees(map: &Grid<bool>, slope: Xy) -> usize {
+    (0..map.height() as i32)
+        .step_by(slope.y as usize)
+        .enumerate()
+        .filter(|&(t, y)| map.get_wrapping(Xy::new(t as i32 * slope.x, y)) == Some(&true))
+        .count()
+}
+
+// Solver //
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = Grid<bool>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        Grid::parse(3, bufin, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(count_trees(input, Xy::new(3, 1)))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        let slopes = [
+            Xy::new(1, 1),
+            Xy::new(3, 1),
+            Xy::new(5, 1),
+            Xy::new(7, 1),
+            Xy::new(1, 2),
+        ];
+        let mut product = 1;
+        for slope in slopes {
+            let trees = count_trees(input, slope);
+            info!("slope x {} y {} trees {}", slope.x, slope.y, trees);
+            product *= trees;
+        }
+        Ok(product)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day03::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day03::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#\n";
+    assert_eq!(solve_a(input)?, 7);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#\n";
+    assert_eq!(solve_b(input)?, 336);
+    Ok(())
+}
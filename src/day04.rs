@@ -0,0 +1,565 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{bail, ensure, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+
+// Schema //
+
+/// The rules of the puzzle, used by default
+pub const PUZZLE_SCHEMA: &str = r##"
+[[field]]
+name = "byr"
+check = "year"
+min = 1920
+max = 2002
+
+[[field]]
+name = "iyr"
+check = "year"
+min = 2010
+max = 2020
+
+[[field]]
+name = "eyr"
+check = "year"
+min = 2020
+max = 2030
+
+[[field]]
+name = "hgt"
+check = "height"
+units = { cm = [150, 193], in = [59, 76] }
+
+[[field]]
+name = "hcl"
+check = "regex"
+pattern = "#[0-9a-f]{6}"
+
+[[field]]
+name = "ecl"
+check = "oneof"
+values = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
+
+[[field]]
+name = "pid"
+check = "regex"
+pattern = "[0-9]{9}"
+
+[[field]]
+name = "cid"
+optional = true
+check = "any"
+"##;
+
+/// Regex that has to match the whole value
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Pattern(Regex);
+
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ok(Pattern(Regex::new(&format!("^(?:{})$", s))?))
+    }
+}
+
+/// How the value of a field is checked
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "check", rename_all = "lowercase")]
+pub enum Check {
+    /// Anything goes, as long as the field is there
+    Any,
+    /// Year in the inclusive range
+    Year {
+        min: i32,
+        max: i32,
+    },
+    /// Number followed by one of the units, in the inclusive range of
+    /// that unit
+    Height {
+        units: BTreeMap<String, (i32, i32)>,
+    },
+    Regex {
+        pattern: Pattern,
+    },
+    OneOf {
+        values: BTreeSet<String>,
+    },
+}
+
+impl Check {
+    /// Why the value is not valid, if it isn't
+    pub fn reason(&self, value: &str) -> Option<String> {
+        match self {
+            Check::Any => None,
+            Check::Year { min, max } => match value.parse::<i32>() {
+                Ok(year) if (*min..=*max).contains(&year) => None,
+                Ok(_) => Some(format!("not in {}-{}", min, max)),
+                Err(_) => Some("not a year".to_string()),
+            },
+            Check::Height { units } => {
+                let digits = value.find(|c: char| !c.is_ascii_digit());
+                let (num, unit) = value.split_at(digits.unwrap_or(value.len()));
+                let (min, max) = match units.get(unit) {
+                    Some(range) => range,
+                    None => {
+                        let units = units.keys().cloned().collect::<Vec<_>>();
+                        return Some(format!("unit not one of {}", units.join(", ")));
+                    }
+                };
+                match num.parse::<i32>() {
+                    Ok(num) if (*min..=*max).contains(&num) => None,
+                    Ok(_) => Some(format!("not in {}-{}{}", min, max, unit)),
+                    Err(_) => Some("not a number".to_string()),
+                }
+            }
+            Check::Regex { pattern } => {
+                if pattern.0.is_match(value) {
+                    None
+                } else {
+                    // Remove the anchors we added
+                    let re = pattern.0.as_str();
+                    Some(format!("doesn't match {}", &re[4..re.len() - 2]))
+                }
+            }
+            Check::OneOf { values } => {
+                if values.contains(value) {
+                    None
+                } else {
+                    let values = values.iter().cloned().collect::<Vec<_>>();
+                    Some(format!("not one of {}", values.join(", ")))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldSpec {
+    pub name: String,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(flatten)]
+    pub check: Check,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    Missing(String),
+    Invalid {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl Violation {
+    pub fn field(&self) -> &str {
+        match self {
+            Violation::Missing(field) => field,
+            Violation::Invalid { field, .. } => field,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Missing(field) => write!(f, "{} is missing", field),
+            Violation::Invalid {
+                field,
+                value,
+                reason,
+            } => write!(f, "{} {:?} {}", field, value, reason),
+        }
+    }
+}
+
+/// Description of the fields of a valid passport
+#[derive(Debug, Clone, Deserialize)]
+pub struct Schema {
+    #[serde(rename = "field", default)]
+    pub fields: Vec<FieldSpec>,
+}
+
+impl Default for Schema {
+    fn default() -> Schema {
+        lazy_static! {
+            static ref PUZZLE: Schema = Schema::from_toml(PUZZLE_SCHEMA).unwrap();
+        }
+        PUZZLE.clone()
+    }
+}
+
+impl Schema {
+    pub fn from_toml(contents: &str) -> Result<Schema> {
+        let schema: Schema = toml::from_str(contents)?;
+        let mut names = BTreeSet::new();
+        for field in &schema.fields {
+            ensure!(
+                names.insert(&field.name),
+                "field {} is described more than once",
+                field.name
+            );
+        }
+        Ok(schema)
+    }
+
+    pub fn load(path: &Path) -> Result<Schema> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("error reading {}", path.display()))?;
+        Schema::from_toml(&contents).with_context(|| format!("error parsing {}", path.display()))
+    }
+
+    /// The required fields that the passport doesn't have
+    pub fn missing(&self, passport: &Passport) -> Vec<Violation> {
+        self.fields
+            .iter()
+            .filter(|f| !f.optional && passport.get(&f.name).is_none())
+            .map(|f| Violation::Missing(f.name.clone()))
+            .collect()
+    }
+
+    /// Everything that is wrong with the passport, in the order of the
+    /// schema
+    pub fn validate(&self, passport: &Passport) -> Vec<Violation> {
+        self.fields
+            .iter()
+            .filter_map(|f| match passport.get(&f.name) {
+                None if f.optional => None,
+                None => Some(Violation::Missing(f.name.clone())),
+                Some(value) => f.check.reason(value).map(|reason| Violation::Invalid {
+                    field: f.name.clone(),
+                    value: value.to_string(),
+                    reason,
+                }),
+            })
+            .collect()
+    }
+
+    pub fn report(&self, passports: &[Passport]) -> Report {
+        Report {
+            total: passports.len(),
+            invalid: passports
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i + 1, self.validate(p)))
+                .filter(|(_, violations)| !violations.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Why each invalid passport failed; passports are numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub total: usize,
+    pub invalid: Vec<(usize, Vec<Violation>)>,
+}
+
+impl Report {
+    pub fn valid(&self) -> usize {
+        self.total - self.invalid.len()
+    }
+
+    /// Number of passports that failed because of each field
+    pub fn by_field(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for (_, violations) in &self.invalid {
+            for v in violations {
+                *counts.entry(v.field()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} of {} passports are valid", self.valid(), self.total)?;
+        for (i, violations) in &self.invalid {
+            let reasons = violations.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            writeln!(f, "passport {}: {}", i, reasons.join("; "))?;
+        }
+        for (field, count) in self.by_field() {
+            writeln!(f, "{}: invalid in {}", field, count)?;
+        }
+        Ok(())
+    }
+}
+
+// Passport //
+
+#[derive(Default, Debug, Clone)]
+pub struct Passport {
+    entries: BTreeMap<String, String>,
+}
+
+impl Passport {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(|v| v.as_str())
+    }
+
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.entries.insert(name.to_string(), value.to_string());
+    }
+
+    /// Has all fields required by the puzzle schema
+    pub fn has_fields(&self) -> bool {
+        Schema::default().missing(self).is_empty()
+    }
+
+    /// Check the passport against the puzzle schema, reporting all
+    /// violations in the error
+    pub fn analyse(&self) -> Result<()> {
+        let violations = Schema::default().validate(self);
+        if !violations.is_empty() {
+            let reasons = violations.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            bail!("{}", reasons.join("; "));
+        }
+        Ok(())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.analyse().is_ok()
+    }
+}
+
+// Solver //
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut passports = vec![];
+        let mut passport = Passport::default();
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            if line.is_empty() {
+                // passport is complete
+                passports.push(passport);
+                passport = Passport::default();
+                continue;
+            }
+            // one more field line, with space-separated "key:value" fields
+            let mut offset = 0;
+            for entry in line.split(' ') {
+                match entry.split_once(':') {
+                    Some((key, value)) if !key.is_empty() => passport.insert(key, value),
+                    _ => {
+                        let expected = "a \"key:value\" field";
+                        let err = ParseError::at_offset(4, &line, offset, expected);
+                        return Err(err.at_line(i + 1).into());
+                    }
+                }
+                offset += entry.len() + 1;
+            }
+        }
+        passports.push(passport);
+        Ok(passports)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        let schema = Schema::default();
+        Ok(input
+            .iter()
+            .filter(|p| schema.missing(p).is_empty())
+            .count())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(Schema::default().report(input).valid())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day04::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day04::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\niyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\nhcl:#cfa07d byr:1929\n\nhcl:#ae17e1 iyr:2013\neyr:2024\necl:brn pid:760753108 byr:1931\nhgt:179cm\n\nhcl:#cfa07d eyr:2025 pid:166559648\niyr:2011 ecl:brn hgt:59in\n";
+    assert_eq!(solve_a(input)?, 2);
+    // Malformed fields are errors: a double space and a token without ':'
+    let err = Day04::parse(&b"byr:1937\n\nhgt:170cm  pid:1\n"[..]).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (3, 11));
+    let err = Day04::parse(&b"byr:1937 ab\n"[..]).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (1, 10));
+    // Columns count characters, not bytes
+    let err = Day04::parse("hcl:#éé ab\n".as_bytes()).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (1, 9));
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\niyr:2019\nhcl:#602927 eyr:1967 hgt:170cm\necl:grn pid:012533040 byr:1946\n\nhcl:dab227 iyr:2012\necl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277\n\nhgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023\npid:3556412378 byr:2007\n\npid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f\n\neyr:2029 ecl:blu cid:129 byr:1989\niyr:2014 pid:896056539 hcl:#a97842 hgt:165cm\n\nhcl:#888785\nhgt:164cm byr:2001 iyr:2015 cid:88\npid:545766238 ecl:hzl\neyr:2022\n\niyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719\n";
+    assert_eq!(solve_b(input)?, 4);
+    Ok(())
+}
+
+#[test]
+fn test2_b() -> Result<()> {
+    let valid_passport = {
+        let mut p = Passport::default();
+        p.insert("byr", "1950");
+        p.insert("iyr", "2015");
+        p.insert("eyr", "2025");
+        p.insert("hgt", "175cm");
+        p.insert("hcl", "#0000ff");
+        p.insert("ecl", "gry");
+        p.insert("pid", "123456789");
+        p
+    };
+    assert!(valid_passport.is_valid());
+    for (name, val, valid) in &vec![
+        ("byr", "1919", false),
+        ("byr", "1920", true),
+        ("byr", "2002", true),
+        ("byr", "2003", false),
+        ("iyr", "2009", false),
+        ("iyr", "2010", true),
+        ("iyr", "2020", true),
+        ("iyr", "2021", false),
+        ("eyr", "2019", false),
+        ("eyr", "2020", true),
+        ("eyr", "2030", true),
+        ("eyr", "2031", false),
+        ("hgt", "149cm", false),
+        ("hgt", "150cm", true),
+        ("hgt", "193cm", true),
+        ("hgt", "194cm", false),
+        ("hgt", "58in", false),
+        ("hgt", "59in", true),
+        ("hgt", "76in", true),
+        ("hgt", "77in", false),
+        ("hcl", "#000000", true),
+        ("hcl", "#ffffff", true),
+        ("hcl", "z000000", false),
+        ("hcl", "#0000000", false),
+        ("hcl", "#gggggg", false),
+        ("ecl", "aaa", false),
+        ("ecl", "amb", true),
+        ("ecl", "blu", true),
+        ("ecl", "brn", true),
+        ("ecl", "gry", true),
+        ("ecl", "grn", true),
+        ("ecl", "hzl", true),
+        ("ecl", "oth", true),
+        ("ecl", "zzz", false),
+        ("pid", "0", false),
+        ("pid", "012345678", true),
+        ("pid", "a12345678", false),
+        ("pid", "0123456789", false),
+        ("pid", "01234567", false),
+    ] {
+        let mut p = valid_passport.clone();
+        p.insert(name, val);
+        if *valid {
+            assert_eq!(p.analyse()?, ());
+        } else {
+            assert!(p.analyse().is_err());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_schema() -> Result<()> {
+    let schema = Schema::from_toml(
+        r#"
+        [[field]]
+        name = "hgt"
+        check = "height"
+        units = { cm = [150, 193] }
+
+        [[field]]
+        name = "ecl"
+        check = "oneof"
+        values = ["amb", "blu"]
+
+        [[field]]
+        name = "pid"
+        check = "regex"
+        pattern = "[0-9]{9}"
+
+        [[field]]
+        name = "cid"
+        optional = true
+        check = "any"
+        "#,
+    )?;
+    let mut p = Passport::default();
+    p.insert("hgt", "60in");
+    p.insert("ecl", "zzz");
+    p.insert("cid", "whatever");
+    assert_eq!(
+        schema.validate(&p),
+        vec![
+            Violation::Invalid {
+                field: "hgt".to_string(),
+                value: "60in".to_string(),
+                reason: "unit not one of cm".to_string(),
+            },
+            Violation::Invalid {
+                field: "ecl".to_string(),
+                value: "zzz".to_string(),
+                reason: "not one of amb, blu".to_string(),
+            },
+            Violation::Missing("pid".to_string()),
+        ]
+    );
+    p.insert("pid", "0123456789");
+    assert_eq!(
+        schema.validate(&p)[2].to_string(),
+        "pid \"0123456789\" doesn't match [0-9]{9}"
+    );
+    assert!(Schema::from_toml(
+        "[[field]]\nname = \"a\"\ncheck = \"any\"\n[[field]]\nname = \"a\"\ncheck = \"any\"\n"
+    )
+    .is_err());
+    assert!(Schema::from_toml("[[field]]\nname = \"a\"\n").is_err());
+    assert!(
+        Schema::from_toml("[[field]]\nname = \"a\"\ncheck = \"regex\"\npattern = \"(\"\n").is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_report() -> Result<()> {
+    let input: &[u8] = b"eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\npid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f\n\nhgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023\npid:3556412378\n";
+    let passports = Day04::parse(input)?;
+    let report = Schema::default().report(&passports);
+    assert_eq!(report.valid(), 1);
+    assert_eq!(report.invalid[0].0, 1);
+    assert_eq!(report.invalid[1].1.len(), 7);
+    assert_eq!(
+        report.to_string().lines().nth(1),
+        Some("passport 1: eyr \"1972\" not in 2020-2030; hgt \"170\" unit not one of cm, in; pid \"186cm\" doesn't match [0-9]{9}")
+    );
+    assert_eq!(report.by_field()["eyr"], 2);
+    assert_eq!(report.by_field().get("byr"), Some(&1));
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Only keep the max value inside the give Option
+pub fn max_set<T: Ord>(max: &mut Option<T>, item: T) {
+    if let Some(v) = max {
+        if *v < item {
+            *max = Some(item);
+        }
+    } else {
+        *max = Some(item);
+    }
+}
+
+/// Boarding pass
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BPass {
+    pub num: u32,
+}
+
+impl BPass {
+    pub fn new(num: u32) -> BPass {
+        BPass { num }
+    }
+}
+
+impl FromStr for BPass {
+    type Err = ParseError;
+    /// Parse the 7 F/B row letters followed by the 3 L/R column letters;
+    /// errors are at line 1
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut num = 0;
+        let mut chars = s.chars();
+        for i in 0..10 {
+            let (zero, one, expected) = if i < 7 {
+                ('F', 'B', "F or B")
+            } else {
+                ('L', 'R', "L or R")
+            };
+            match chars.next() {
+                Some(c) if c == zero => {}
+                Some(c) if c == one => num |= 1 << (9 - i),
+                _ => return Err(ParseError::new(5, 1, i + 1, s, expected)),
+            }
+        }
+        if chars.next().is_some() {
+            return Err(ParseError::new(5, 1, 11, s, "end of line"));
+        }
+        Ok(BPass::new(num))
+    }
+}
+
+// Solver //
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = BTreeSet<BPass>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut bpasses = BTreeSet::new();
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            bpasses.insert(line.parse::<BPass>().map_err(|e| e.at_line(i + 1))?);
+        }
+        Ok(bpasses)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        let mut max = None;
+        for bpass in input {
+            max_set(&mut max, bpass);
+        }
+        Ok(max.ok_or_else(|| anyhow!("empty bpasses"))?.num)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        let mut prev = input
+            .iter()
+            .next()
+            .ok_or_else(|| anyhow!("empty bpasses"))?;
+        for bpass in input.iter().skip(1) {
+            if bpass.num - prev.num == 2 {
+                return Ok(prev.num + 1);
+            }
+            prev = bpass;
+        }
+        Err(anyhow!("seat not found"))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<u32> {
+    Day05::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<u32> {
+    Day05::run2(bufin)
+}
+
+#[test]
+fn test_bpass() -> Result<()> {
+    let input = "BFFFBBFRRR";
+    assert_eq!(input.parse::<BPass>()?, BPass::new(567));
+    let input = "FFFBBBFRRR";
+    assert_eq!(input.parse::<BPass>()?, BPass::new(119));
+    let input = "BBFFBBFRLL";
+    assert_eq!(input.parse::<BPass>()?, BPass::new(820));
+    let err = Day05::parse(&b"BFFFBBFRRR\nBFFFXBFRRR\n"[..]).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (2, 5));
+    assert_eq!(err.expected, "F or B");
+    let err = "BFFFBBFRR".parse::<BPass>().unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (10, "L or R"));
+    let err = "BFFFBBFRRRL".parse::<BPass>().unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (11, "end of line"));
+    Ok(())
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n";
+    assert_eq!(solve_a(input)?, 820);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::Solver;
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+pub fn gt0(v: u32) -> usize {
+    if v > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+pub fn char_to_mask(c: char) -> u32 {
+    1 << (c as u32 - 'a' as u32)
+}
+
+pub fn bitsum(v: u32) -> usize {
+    ('a'..='z').map(|c| gt0(v & char_to_mask(c))).sum()
+}
+
+// Solver //
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    /// The answers of each group, one string per person
+    type Input = Vec<Vec<String>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut groups = vec![];
+        let mut group = vec![];
+        for line_opt in bufin.lines() {
+            let line = line_opt?;
+            if line.is_empty() {
+                // group is complete
+                groups.push(group);
+                group = vec![];
+            } else {
+                group.push(line);
+            }
+        }
+        // add last group
+        groups.push(group);
+        Ok(groups)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(input
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .flat_map(|l| l.chars())
+                    .collect::<BTreeSet<_>>()
+                    .len()
+            })
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(input
+            .iter()
+            .map(|group| {
+                bitsum(group.iter().fold(u32::MAX, |group, line| {
+                    group & line.chars().fold(0_u32, |acc, c| acc | char_to_mask(c))
+                }))
+            })
+            .sum())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day06::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day06::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n";
+    assert_eq!(solve_a(input)?, 11);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n";
+    assert_eq!(solve_b(input)?, 6);
+    Ok(())
+}
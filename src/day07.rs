@@ -0,0 +1,374 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Error, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bag {
+    pub name: String,
+}
+
+impl Bag {
+    pub fn new(name: &str) -> Bag {
+        Bag {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl FromStr for Bag {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inputs = s.split(' ').collect::<Vec<_>>();
+        Ok(Bag {
+            name: format!("{} {}", inputs[0], inputs[1]),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Rule {
+    pub bag: Bag,
+    pub contents: Vec<(usize, Bag)>,
+}
+
+impl Rule {
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a (usize, Bag)> + 'a {
+        self.contents.iter()
+    }
+}
+
+/// Error for rules that end up with a bag inside itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// The bags in the cycle, starting and ending with the same one
+    pub cycle: Vec<Bag>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bag inside itself: ")?;
+        for (i, bag) in self.cycle.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", bag)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Graph of the rules, with edges going both ways: from each bag to the
+/// bags it contains, and from each bag to the bags that contain it
+#[derive(Default, Debug)]
+pub struct BagGraph {
+    /// Bags that directly contain each bag, with their quantities
+    pub outer: BTreeMap<Bag, Vec<(usize, Bag)>>,
+    /// Bags directly contained by each bag, with their quantities
+    pub inner: BTreeMap<Bag, Vec<(usize, Bag)>>,
+}
+
+impl BagGraph {
+    pub fn insert(&mut self, rule: Rule) {
+        let innervec = self.inner.entry(rule.bag.clone()).or_default();
+        for (innernum, innerbag) in rule.iter() {
+            innervec.push((*innernum, innerbag.clone()));
+            let outervec = self.outer.entry(innerbag.clone()).or_default();
+            outervec.push((*innernum, rule.bag.clone()));
+        }
+    }
+
+    /// All bags that appear in the rules
+    pub fn bags(&self) -> BTreeSet<&Bag> {
+        self.inner.keys().chain(self.outer.keys()).collect()
+    }
+
+    /// Bags directly contained by `bag`
+    pub fn contents_of(&self, bag: &Bag) -> &[(usize, Bag)] {
+        self.inner.get(bag).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Bags that directly contain `bag`
+    pub fn containers_of(&self, bag: &Bag) -> &[(usize, Bag)] {
+        self.outer.get(bag).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Bags that end up containing `bag`, directly or not
+    pub fn all_containers(&self, bag: &Bag) -> BTreeSet<Bag> {
+        let mut visited = BTreeSet::new();
+        let mut frontier = vec![bag];
+        while let Some(current) = frontier.pop() {
+            for (_, outer) in self.containers_of(current) {
+                if visited.insert(outer.clone()) {
+                    frontier.push(outer);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Total number of bags inside `bag`; fails on cycles and on totals
+    /// that don't fit a usize
+    pub fn count_inside(&self, bag: &Bag) -> Result<usize> {
+        let mut totals = BTreeMap::<&Bag, usize>::new();
+        // In post-order, the contents of a bag are counted before it
+        for current in self.postorder(std::iter::once(bag))? {
+            let mut total = 0_usize;
+            for (num, inner) in self.contents_of(current) {
+                total = totals[inner]
+                    .checked_add(1)
+                    .and_then(|t| t.checked_mul(*num))
+                    .and_then(|t| t.checked_add(total))
+                    .ok_or_else(|| anyhow!("too many bags inside {}", current))?;
+            }
+            totals.insert(current, total);
+        }
+        Ok(totals[bag])
+    }
+
+    /// All bags, each one before the bags it contains
+    pub fn toposort(&self) -> Result<Vec<&Bag>, CycleError> {
+        let mut order = self.postorder(self.bags().into_iter())?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Bags reachable from `roots` through their contents, each one after
+    /// the bags it contains
+    fn postorder<'a>(
+        &'a self,
+        roots: impl Iterator<Item = &'a Bag>,
+    ) -> Result<Vec<&'a Bag>, CycleError> {
+        let mut done = BTreeSet::<&Bag>::new();
+        let mut order = vec![];
+        for root in roots {
+            if done.contains(root) {
+                continue;
+            }
+            // The bags being visited, with the index of their next content
+            let mut path = vec![(root, 0)];
+            let mut onpath = BTreeSet::from([root]);
+            while let Some(&(current, i)) = path.last() {
+                if let Some((_, inner)) = self.contents_of(current).get(i) {
+                    path.last_mut().unwrap().1 += 1;
+                    if done.contains(inner) {
+                        continue;
+                    }
+                    if onpath.contains(inner) {
+                        let start = path.iter().position(|(b, _)| *b == inner).unwrap();
+                        let mut cycle = path[start..]
+                            .iter()
+                            .map(|(b, _)| (*b).clone())
+                            .collect::<Vec<_>>();
+                        cycle.push(inner.clone());
+                        return Err(CycleError { cycle });
+                    }
+                    path.push((inner, 0));
+                    onpath.insert(inner);
+                } else {
+                    path.pop();
+                    onpath.remove(current);
+                    done.insert(current);
+                    order.push(current);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Graphviz representation, with the quantities as edge labels
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bags {\n");
+        for bag in self.bags() {
+            dot.push_str(&format!("    {:?};\n", bag.name));
+        }
+        for (outer, contents) in &self.inner {
+            for (num, inner) in contents {
+                dot.push_str(&format!(
+                    "    {:?} -> {:?} [label={}];\n",
+                    outer.name, inner.name, num
+                ));
+            }
+        }
+        dot.push('}');
+        dot.push('\n');
+        dot
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RULE_RE: Regex =
+                Regex::new(r"^(?P<bag>[^ ]+ [^ ]+) bags contain (?P<contents>.*)\.$").unwrap();
+        }
+        let rule_m = RULE_RE
+            .captures(s)
+            .ok_or_else(|| ParseError::new(7, 1, 1, s, "\"<bag> bags contain <contents>.\""))?;
+        let contents = rule_m.name("contents").unwrap();
+        lazy_static! {
+            static ref VEC_RE: Regex =
+                Regex::new(r"^\s*(?P<num>[0-9]+) (?P<bag>[^ ]+ [^ ]+) bags?$").unwrap();
+        }
+        let mut bagvec = vec![];
+        let mut offset = contents.start();
+        for entry in contents.as_str().split(',') {
+            if entry == "no other bags" {
+                break;
+            }
+            let entry_m = VEC_RE
+                .captures(entry)
+                .ok_or_else(|| ParseError::at_offset(7, s, offset, "\"<number> <bag> bags\""))?;
+            bagvec.push((
+                entry_m["num"]
+                    .parse()
+                    .map_err(|_| ParseError::at_offset(7, s, offset, "a number"))?,
+                Bag::new(&entry_m["bag"]),
+            ));
+            offset += entry.len() + 1;
+        }
+        Ok(Rule {
+            bag: Bag::new(&rule_m["bag"]),
+            contents: bagvec,
+        })
+    }
+}
+
+// Solver //
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Input = BagGraph;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut graph = BagGraph::default();
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            graph.insert(line.parse::<Rule>().map_err(|e| e.at_line(i + 1))?);
+        }
+        Ok(graph)
+    }
+
+    fn part1(graph: &Self::Input) -> Result<Self::Answer1> {
+        Ok(graph.all_containers(&Bag::new("shiny gold")).len())
+    }
+
+    fn part2(graph: &Self::Input) -> Result<Self::Answer2> {
+        graph.count_inside(&Bag::new("shiny gold"))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day07::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day07::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"light red bags contain 1 bright white bag, 2 muted yellow bags.\ndark orange bags contain 3 bright white bags, 4 muted yellow bags.\nbright white bags contain 1 shiny gold bag.\nmuted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\nshiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\ndark olive bags contain 3 faded blue bags, 4 dotted black bags.\nvibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\nfaded blue bags contain no other bags.\ndotted black bags contain no other bags.\n";
+    assert_eq!(solve_a(input)?, 4);
+    Ok(())
+}
+
+#[test]
+fn test_rule() -> Result<()> {
+    let rule = "light red bags contain 1 bright white bag, 2 muted yellow bags.".parse::<Rule>()?;
+    assert_eq!(rule.bag, Bag::new("light red"));
+    let mut it = rule.iter();
+    assert_eq!(it.next().unwrap(), &(1, Bag::new("bright white")));
+    assert_eq!(it.next().unwrap(), &(2, Bag::new("muted yellow")));
+    // Columns count characters, not bytes
+    let err = "light red bags contain 1 brïght white bag, 2 x."
+        .parse::<Rule>()
+        .unwrap_err();
+    assert_eq!(err.column, 43);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"light red bags contain 1 bright white bag, 2 muted yellow bags.\ndark orange bags contain 3 bright white bags, 4 muted yellow bags.\nbright white bags contain 1 shiny gold bag.\nmuted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\nshiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\ndark olive bags contain 3 faded blue bags, 4 dotted black bags.\nvibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\nfaded blue bags contain no other bags.\ndotted black bags contain no other bags.\n";
+    assert_eq!(solve_b(input)?, 32);
+    Ok(())
+}
+
+#[test]
+fn test2_b() -> Result<()> {
+    let input: &[u8] = b"shiny gold bags contain 2 dark red bags.\ndark red bags contain 2 dark orange bags.\ndark orange bags contain 2 dark yellow bags.\ndark yellow bags contain 2 dark green bags.\ndark green bags contain 2 dark blue bags.\ndark blue bags contain 2 dark violet bags.\ndark violet bags contain no other bags.\n";
+    assert_eq!(solve_b(input)?, 126);
+    Ok(())
+}
+
+#[test]
+fn test_graph() -> Result<()> {
+    let input: &[u8] = b"light red bags contain 1 bright white bag, 2 muted yellow bags.\nbright white bags contain 1 shiny gold bag.\nmuted yellow bags contain 2 shiny gold bags.\nshiny gold bags contain no other bags.\n";
+    let graph = Day07::parse(input)?;
+    let gold = Bag::new("shiny gold");
+    assert_eq!(
+        graph.containers_of(&gold),
+        &[(1, Bag::new("bright white")), (2, Bag::new("muted yellow"))]
+    );
+    assert!(graph.contents_of(&gold).is_empty());
+    assert_eq!(graph.count_inside(&Bag::new("light red"))?, 8);
+    let order = graph.toposort()?;
+    assert_eq!(order.first(), Some(&&Bag::new("light red")));
+    assert_eq!(order.last(), Some(&&gold));
+    assert_eq!(
+        graph.to_dot().lines().nth(5),
+        Some("    \"bright white\" -> \"shiny gold\" [label=1];")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cycle() -> Result<()> {
+    let input: &[u8] = b"shiny gold bags contain 2 dark red bags.\ndark red bags contain 1 dark blue bag, 2 dark orange bags.\ndark orange bags contain 1 shiny gold bag.\n";
+    let graph = Day07::parse(input)?;
+    let err = graph.count_inside(&Bag::new("shiny gold")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bag inside itself: shiny gold -> dark red -> dark orange -> shiny gold"
+    );
+    assert!(graph.toposort().is_err());
+    assert_eq!(graph.all_containers(&Bag::new("dark red")).len(), 3);
+    // 1000^7 bags don't fit
+    let mut graph = BagGraph::default();
+    for i in 0..7 {
+        graph.insert(Rule {
+            bag: Bag::new(&format!("level {}", i)),
+            contents: vec![(1000, Bag::new(&format!("level {}", i + 1)))],
+        });
+    }
+    assert_eq!(
+        graph.count_inside(&Bag::new("level 1"))?,
+        1001001001001001000
+    );
+    let err = graph.count_inside(&Bag::new("level 0")).unwrap_err();
+    assert_eq!(err.to_string(), "too many bags inside level 0");
+    Ok(())
+}
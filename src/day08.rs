@@ -0,0 +1,103 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::vm::{self, Instr, OpTable, Outcome, Vm};
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::io::BufRead;
+
+// Process, etc //
+
+/// Find the nop or jmp that, when swapped, makes the program halt; returns
+/// the accumulator at the end
+pub fn fix(vm: &mut Vm) -> Result<i32> {
+    for i in 0..vm.program.len() {
+        let old = vm.program[i].clone();
+        let swapped = match old.op.as_str() {
+            "nop" => "jmp",
+            "jmp" => "nop",
+            _ => continue,
+        };
+        vm.patch(i, Instr::new(swapped, old.arg))?;
+        if vm.run_all() == Outcome::Halt {
+            return Ok(vm.cpu.acc);
+        }
+        vm.patch(i, old)?;
+    }
+    Err(anyhow!("no single swap makes the program halt"))
+}
+
+// Solver //
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Input = Vec<Instr>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        vm::parse_program(8, bufin, &OpTable::default())
+    }
+
+    fn part1(program: &Self::Input) -> Result<Self::Answer1> {
+        let mut vm = Vm::new(OpTable::default(), program.clone())?;
+        match vm.run_all() {
+            Outcome::Loop(_) => Ok(vm.cpu.acc),
+            outcome => Err(anyhow!("expected a loop, got {}", outcome)),
+        }
+    }
+
+    fn part2(program: &Self::Input) -> Result<Self::Answer2> {
+        let mut vm = Vm::new(OpTable::default(), program.clone())?;
+        fix(&mut vm)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i32> {
+    Day08::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i32> {
+    Day08::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+    assert_eq!(solve_a(input)?, 5);
+    Ok(())
+}
+
+#[test]
+fn test2() -> Result<()> {
+    for (s, instr) in [
+        ("nop +0", Instr::new("nop", 0)),
+        ("acc +1", Instr::new("acc", 1)),
+        ("acc -5", Instr::new("acc", -5)),
+        ("jmp +700", Instr::new("jmp", 700)),
+        ("jmp -900", Instr::new("jmp", -900)),
+    ] {
+        assert_eq!(s.parse::<Instr>()?, instr);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_error() {
+    use crate::ParseError;
+    let input: &[u8] = b"nop +0\nacc +1\nxyz +3\n";
+    let err = Day08::parse(input).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(*err, ParseError::new(8, 3, 1, "xyz +3", "acc, jmp or nop"));
+    let err = "acc 1x".parse::<Instr>().unwrap_err();
+    assert_eq!(err.column, 5);
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+    assert_eq!(solve_b(input)?, 8);
+    Ok(())
+}
@@ -2,16 +2,18 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-use anyhow::{Context, Result};
+use crate::error::parse_line;
+use crate::Solver;
+use anyhow::{anyhow, Context, Result};
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::VecDeque;
-use std::io::{stdin, BufRead};
+use std::io::BufRead;
 
 // NotsumHunter //
 
 #[derive(Clone)]
-struct NotsumHunter {
+pub struct NotsumHunter {
     preamble: usize,
     numvec: VecDeque<i64>,
     // Key is lower number, value is sum with numbers higher than key
@@ -19,7 +21,7 @@ struct NotsumHunter {
 }
 
 impl NotsumHunter {
-    fn new(preamble: usize) -> NotsumHunter {
+    pub fn new(preamble: usize) -> NotsumHunter {
         NotsumHunter {
             preamble,
             numvec: Default::default(),
@@ -27,7 +29,7 @@ impl NotsumHunter {
         }
     }
 
-    fn insert(&mut self, num: i64) -> Result<bool> {
+    pub fn insert(&mut self, num: i64) -> Result<bool> {
         let mut valid = false;
         if self.numvec.len() >= self.preamble {
             // preamble done, check if valid
@@ -73,7 +75,7 @@ impl NotsumHunter {
 // KeyHunter //
 
 #[derive(Default, Clone)]
-struct KeyHunter {
+pub struct KeyHunter {
     allnums: Vec<i64>,
 }
 
@@ -119,27 +121,80 @@ impl KeyHunter {
 
 // Process, etc //
 
-fn process(preamble: usize, bufin: impl BufRead) -> Result<i64> {
-    let mut notnum = NotsumHunter::new(preamble);
-    let mut key = KeyHunter::default();
-    let mut invalid_opt = None;
-    for line_opt in bufin.lines() {
-        let line = line_opt?;
-        let num = line.parse()?;
-        if invalid_opt.is_none() && !notnum.insert(num)? {
-            invalid_opt = Some(num);
+pub fn find_invalid(preamble: usize, nums: &[i64]) -> Result<i64> {
+    let mut state = NotsumHunter::new(preamble);
+    for &num in nums {
+        if !state.insert(num)? {
+            return Ok(num);
         }
+    }
+    Err(anyhow!("all numbers are valid!"))
+}
+
+pub fn find_weakness(preamble: usize, nums: &[i64]) -> Result<i64> {
+    let invalid = find_invalid(preamble, nums)?;
+    let mut key = KeyHunter::default();
+    for &num in nums {
         key.insert(num);
     }
-    let invalid = invalid_opt.context("all numbers are valid")?;
     key.hunt(invalid)
 }
 
+// Solver //
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut nums = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            nums.push(parse_line(9, i + 1, &line_opt?)?);
+        }
+        Ok(nums)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<Self::Answer1> {
+        find_invalid(25, nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<Self::Answer2> {
+        find_weakness(25, nums)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day09::run1(bufin)
+}
+
+pub fn solve_a_with(preamble: usize, bufin: impl BufRead) -> Result<i64> {
+    find_invalid(preamble, &Day09::parse(bufin)?)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day09::run2(bufin)
+}
+
+pub fn solve_b_with(preamble: usize, bufin: impl BufRead) -> Result<i64> {
+    find_weakness(preamble, &Day09::parse(bufin)?)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] =
+        b"35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576";
+    assert_eq!(solve_a_with(5, input)?, 127);
+    Ok(())
+}
+
 #[test]
-fn test() -> Result<()> {
+fn test_b() -> Result<()> {
     let input: &[u8] =
         b"35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576";
-    assert_eq!(process(5, input)?, 62);
+    assert_eq!(solve_b_with(5, input)?, 62);
     Ok(())
 }
 
@@ -159,8 +214,3 @@ fn test2() -> Result<()> {
     assert!(!state.insert(50)?);
     Ok(())
 }
-
-fn main() -> Result<()> {
-    println!("{}", process(25, stdin().lock())?);
-    Ok(())
-}
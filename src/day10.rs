@@ -0,0 +1,114 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_line;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+// Process, etc //
+
+pub fn dfs(cache: &mut BTreeMap<usize, i64>, target: i64, nums: &[i64]) -> i64 {
+    if let Some(val) = cache.get(&nums.len()) {
+        return *val;
+    }
+    if nums.len() == 1 {
+        return if nums[0] == target { 1 } else { 0 };
+    }
+    let mut total = 0;
+    for next in 1..=3 {
+        if next < nums.len() && nums[next] - nums[0] <= 3 {
+            total += dfs(cache, target, &nums[next..]);
+        }
+    }
+    cache.insert(nums.len(), total);
+    total
+}
+
+// Solver //
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    /// The adapters, sorted
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut nums = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            nums.push(parse_line(10, i + 1, &line_opt?)?);
+        }
+        nums.sort_unstable();
+        Ok(nums)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<Self::Answer1> {
+        let mut dif1 = 0;
+        let mut dif3 = 1; // my port
+        let mut curr = 0;
+        for &num in nums {
+            match num - curr {
+                1 => {
+                    dif1 += 1;
+                }
+                3 => {
+                    dif3 += 1;
+                }
+                _ => {
+                    return Err(anyhow!("unsupported diff {}", num - curr));
+                }
+            }
+            curr = num;
+        }
+        Ok(dif1 * dif3)
+    }
+
+    fn part2(adapters: &Self::Input) -> Result<Self::Answer2> {
+        let mut nums = vec![0];
+        nums.extend(adapters);
+        let last = nums[nums.len() - 1] + 3;
+        nums.push(last);
+        let mut cache = BTreeMap::new();
+        Ok(dfs(&mut cache, last, &nums))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day10::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day10::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+    assert_eq!(solve_a(input)?, 7 * 5);
+    Ok(())
+}
+
+#[test]
+fn test2_a() -> Result<()> {
+    let input: &[u8] = b"28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n";
+    assert_eq!(solve_a(input)?, 22 * 10);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+    assert_eq!(solve_b(input)?, 8);
+    Ok(())
+}
+
+#[test]
+fn test2_b() -> Result<()> {
+    let input: &[u8] = b"28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n";
+    assert_eq!(solve_b(input)?, 19208);
+    Ok(())
+}
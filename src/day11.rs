@@ -0,0 +1,109 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::grid::{Grid, Xy};
+use crate::Solver;
+use anyhow::Result;
+use std::io::BufRead;
+
+pub struct Ferry {
+    /// Whether each position has a seat or is floor
+    pub seats: Grid<bool>,
+}
+
+impl Ferry {
+    pub fn iter_xy(&self) -> impl Iterator<Item = Xy> + '_ {
+        self.seats.xys().filter(move |&xy| self.seats[xy])
+    }
+
+    /// Iterate over the seats seen from xy; if far is false, only the
+    /// adjacent ones are considered, otherwise we look past the floor.
+    pub fn iter_neighs_xy(&self, xy: Xy, far: bool) -> impl Iterator<Item = Xy> + '_ {
+        Xy::DIRS8.iter().filter_map(move |&dir| {
+            if far {
+                self.seats.ray(xy, dir).find(|&nxy| self.seats[nxy])
+            } else {
+                Some(xy + dir).filter(|&nxy| self.is_seat(nxy))
+            }
+        })
+    }
+
+    pub fn is_seat(&self, xy: Xy) -> bool {
+        self.seats.get(xy) == Some(&true)
+    }
+
+    /// Iterate until stable; an occupied seat is emptied if it sees more
+    /// than max_occupied occupied seats
+    pub fn iter(&self, far: bool, max_occupied: usize) -> i32 {
+        let neighs = self
+            .iter_xy()
+            .map(|xy| (xy, self.iter_neighs_xy(xy, far).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let mut last_occupied = self.seats.map(|_| false);
+        loop {
+            let mut occupied = last_occupied.clone();
+            for (xy, seen) in &neighs {
+                let num = seen.iter().filter(|&&nxy| last_occupied[nxy]).count();
+                occupied[*xy] = if last_occupied[*xy] {
+                    num <= max_occupied
+                } else {
+                    num < 1
+                };
+            }
+            if occupied == last_occupied {
+                return occupied.iter().filter(|(_, &o)| o).count() as i32;
+            }
+            last_occupied = occupied;
+        }
+    }
+}
+
+// Solver //
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Ferry;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let seats = Grid::parse(11, bufin, |c| match c {
+            'L' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Ferry { seats })
+    }
+
+    fn part1(ferry: &Self::Input) -> Result<Self::Answer1> {
+        Ok(ferry.iter(false, 3))
+    }
+
+    fn part2(ferry: &Self::Input) -> Result<Self::Answer2> {
+        Ok(ferry.iter(true, 4))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i32> {
+    Day11::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i32> {
+    Day11::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL\n";
+    assert_eq!(solve_a(input)?, 37);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL\n";
+    assert_eq!(solve_b(input)?, 26);
+    Ok(())
+}
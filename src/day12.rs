@@ -2,8 +2,10 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-use anyhow::{anyhow, Error, Result};
-use std::io::{stdin, BufRead};
+use crate::ParseError;
+use crate::Solver;
+use anyhow::Result;
+use std::io::BufRead;
 use std::ops;
 use std::str::FromStr;
 
@@ -18,15 +20,14 @@ pub enum Dir {
 }
 
 impl FromStr for Dir {
-    type Err = Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let letter = &s[0..1];
-        Ok(match letter {
-            "N" => Dir::N,
-            "S" => Dir::S,
-            "E" => Dir::E,
-            "W" => Dir::W,
-            _ => return Err(anyhow!("invalid Dir {}", s)),
+        Ok(match s.get(0..1) {
+            Some("N") => Dir::N,
+            Some("S") => Dir::S,
+            Some("E") => Dir::E,
+            Some("W") => Dir::W,
+            _ => return Err(ParseError::new(12, 1, 1, s, "N, S, E or W")),
         })
     }
 }
@@ -110,9 +111,9 @@ impl ops::Add<&DirDist> for Xy {
 // Ship //
 
 #[derive(Debug)]
-struct Ship {
-    xy: Xy,
-    dir: Dir,
+pub struct Ship {
+    pub xy: Xy,
+    pub dir: Dir,
 }
 
 impl Default for Ship {
@@ -145,7 +146,7 @@ impl ops::AddAssign<&Act> for Ship {
 // Action //
 
 #[derive(Debug, Clone, Copy)]
-enum Act {
+pub enum Act {
     D(DirDist),
     F(i32),
     L,
@@ -154,10 +155,15 @@ enum Act {
 }
 
 impl FromStr for Act {
-    type Err = Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let letter = &s[0..1];
-        let num = s[1..].parse::<i32>()?;
+        let letter = s
+            .get(0..1)
+            .ok_or_else(|| ParseError::new(12, 1, 1, s, "an action"))?;
+        let num = s[1..]
+            .parse::<i32>()
+            .map_err(|_| ParseError::new(12, 1, 2, s, "a number"))?;
+        let bad_angle = || ParseError::new(12, 1, 2, s, "90, 180 or 270");
         Ok(match letter {
             "N" => Act::D(DirDist::new(s.parse::<Dir>()?, num)),
             "S" => Act::D(DirDist::new(s.parse::<Dir>()?, num)),
@@ -168,39 +174,109 @@ impl FromStr for Act {
                 90 => Act::L,
                 180 => Act::B,
                 270 => Act::R,
-                _ => return Err(anyhow!("invalid angle {}", num)),
+                _ => return Err(bad_angle()),
             },
             "R" => match num {
                 90 => Act::R,
                 180 => Act::B,
                 270 => Act::L,
-                _ => return Err(anyhow!("invalid angle {}", num)),
+                _ => return Err(bad_angle()),
             },
-            _ => return Err(anyhow!("invalid first letter {}", s)),
+            _ => return Err(ParseError::new(12, 1, 1, s, "N, S, E, W, F, L or R")),
         })
     }
 }
 
-// Process, etc //
+// WpShip: ship navigated by waypoint //
+
+#[derive(Debug)]
+pub struct WpShip {
+    pub xy: Xy,
+    pub wp: Xy,
+}
 
-fn process(bufin: impl BufRead) -> Result<i32> {
-    let mut ship = Ship::default();
-    for line_opt in bufin.lines() {
-        let line = line_opt?;
-        let act = line.parse::<Act>()?;
-        ship += &act;
+impl Default for WpShip {
+    fn default() -> WpShip {
+        WpShip {
+            xy: Xy::new(0, 0),
+            wp: Xy::new(10, 1),
+        }
     }
-    Ok(ship.manhattan())
+}
+
+impl WpShip {
+    pub fn manhattan(&self) -> i32 {
+        self.xy.x.abs() + self.xy.y.abs()
+    }
+}
+
+impl ops::AddAssign<&Act> for WpShip {
+    fn add_assign(&mut self, act: &Act) {
+        match act {
+            Act::D(dirdist) => self.wp = self.wp + dirdist,
+            Act::F(dist) => {
+                self.xy = Xy::new(self.xy.x + dist * self.wp.x, self.xy.y + dist * self.wp.y)
+            }
+            Act::L => self.wp = Xy::new(-self.wp.y, self.wp.x),
+            Act::R => self.wp = Xy::new(self.wp.y, -self.wp.x),
+            Act::B => self.wp = Xy::new(-self.wp.x, -self.wp.y),
+        };
+    }
+}
+
+// Solver //
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Vec<Act>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut acts = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            acts.push(line.parse::<Act>().map_err(|e| e.at_line(i + 1))?);
+        }
+        Ok(acts)
+    }
+
+    fn part1(acts: &Self::Input) -> Result<Self::Answer1> {
+        let mut ship = Ship::default();
+        for act in acts {
+            ship += act;
+        }
+        Ok(ship.manhattan())
+    }
+
+    fn part2(acts: &Self::Input) -> Result<Self::Answer2> {
+        let mut ship = WpShip::default();
+        for act in acts {
+            ship += act;
+        }
+        Ok(ship.manhattan())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i32> {
+    Day12::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i32> {
+    Day12::run2(bufin)
 }
 
 #[test]
-fn test() -> Result<()> {
+fn test_a() -> Result<()> {
     let input: &[u8] = b"F10\nN3\nF7\nR90\nF11\n";
-    assert_eq!(process(input)?, 25);
+    assert_eq!(solve_a(input)?, 25);
     Ok(())
 }
 
-fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"F10\nN3\nF7\nR90\nF11\n";
+    assert_eq!(solve_b(input)?, 286);
     Ok(())
 }
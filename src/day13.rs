@@ -0,0 +1,131 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_line;
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::io::BufRead;
+
+// Notes //
+
+/// Our arrival time and the bus ids, with None for the x entries
+#[derive(Debug, Default, Clone)]
+pub struct Notes {
+    pub arrival: i64,
+    pub busses: Vec<Option<i64>>,
+}
+
+// Solver //
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Notes;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut lines_iter = bufin.lines();
+        let arrival = parse_line(
+            13,
+            1,
+            &lines_iter
+                .next()
+                .ok_or_else(|| anyhow!("error reading arrival time"))??,
+        )?;
+        let busses_str = lines_iter
+            .next()
+            .ok_or_else(|| anyhow!("error reading bus times"))??;
+        let mut busses = vec![];
+        let mut column = 1;
+        for s in busses_str.split(',') {
+            if s == "x" {
+                busses.push(None);
+            } else {
+                busses.push(Some(s.parse::<i64>().map_err(|_| {
+                    ParseError::new(13, 2, column, &busses_str, "a bus id or x")
+                })?));
+            }
+            column += s.len() + 1;
+        }
+        Ok(Notes { arrival, busses })
+    }
+
+    fn part1(notes: &Self::Input) -> Result<Self::Answer1> {
+        let arrival = notes.arrival;
+        let mut besttime = i64::MAX;
+        let mut bestbus = i64::MAX;
+        for &bus in notes.busses.iter().flatten() {
+            let bustime = ((arrival / bus) + 1) * bus;
+            if bustime < besttime {
+                besttime = bustime;
+                bestbus = bus;
+            }
+        }
+        Ok(bestbus * (besttime - arrival))
+    }
+
+    fn part2(notes: &Self::Input) -> Result<Self::Answer2> {
+        // Using the chinese remainder theorem:
+        let busses = notes
+            .busses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bus)| bus.map(|n| ((n - i as i64) % n, n)))
+            .collect::<Vec<(i64, i64)>>();
+        let big_n: i64 = busses.iter().map(|(_, n)| n).product();
+        let big_n_is: Vec<i64> = busses.iter().map(|(_, n)| big_n / n).collect();
+        let xis: Vec<i64> = big_n_is
+            .iter()
+            .enumerate()
+            .map(|(i, cp)| {
+                let v = *cp % busses[i].1;
+                let mut xi = 1;
+                loop {
+                    if (xi * v) % busses[i].1 == 1 {
+                        break xi;
+                    }
+                    xi += 1;
+                }
+            })
+            .collect();
+        let sum: i64 = busses
+            .iter()
+            .enumerate()
+            .map(|(i, (b, _))| b * big_n_is[i] * xis[i])
+            .sum();
+        Ok(sum % big_n)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day13::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day13::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"939\n7,13,x,x,59,x,31,19\n";
+    assert_eq!(solve_a(input)?, 295);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"0\n7,13,x,x,59,x,31,19\n";
+    assert_eq!(solve_b(input)?, 1068781);
+    let input: &[u8] = b"0\n67,7,59,61\n";
+    assert_eq!(solve_b(input)?, 754018);
+    let input: &[u8] = b"0\n67,x,7,59,61\n";
+    assert_eq!(solve_b(input)?, 779210);
+    let input: &[u8] = b"0\n67,7,x,59,61\n";
+    assert_eq!(solve_b(input)?, 1261476);
+    let input: &[u8] = b"0\n1789,37,47,1889\n";
+    assert_eq!(solve_b(input)?, 1202161486);
+    Ok(())
+}
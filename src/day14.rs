@@ -0,0 +1,168 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// Cmd //
+
+#[derive(Debug, Clone)]
+pub enum Cmd {
+    /// The 36 mask characters, most significant first
+    Mask(String),
+    Mem(u64, u64),
+}
+
+impl FromStr for Cmd {
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref MEM_RE: Regex =
+                Regex::new(r"^mem\[(?P<index>[0-9]+)\] *= *(?P<value>[0-9]+)$").unwrap();
+        }
+        if let Some(mask) = line.strip_prefix("mask = ") {
+            if let Some(i) = mask.find(|c| !"01X".contains(c)) {
+                return Err(ParseError::new(14, 1, i + 8, line, "0, 1 or X"));
+            }
+            Ok(Cmd::Mask(mask.to_string()))
+        } else {
+            let m = MEM_RE.captures(line).ok_or_else(|| {
+                ParseError::new(
+                    14,
+                    1,
+                    1,
+                    line,
+                    "\"mask = <mask>\" or \"mem[<addr>] = <value>\"",
+                )
+            })?;
+            let num = |name| {
+                let c = m.name(name).unwrap();
+                c.as_str()
+                    .parse::<u64>()
+                    .map_err(|_| ParseError::new(14, 1, c.start() + 1, line, "a 64-bit number"))
+            };
+            Ok(Cmd::Mem(num("index")?, num("value")?))
+        }
+    }
+}
+
+// Solver //
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Vec<Cmd>;
+    type Answer1 = u64;
+    type Answer2 = u128;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut cmds = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            cmds.push(line.parse().map_err(|e: ParseError| e.at_line(i + 1))?);
+        }
+        Ok(cmds)
+    }
+
+    fn part1(cmds: &Self::Input) -> Result<Self::Answer1> {
+        let mut mask_or = 0_u64;
+        let mut mask_and = u64::MAX;
+        let mut mem = BTreeMap::new();
+        for cmd in cmds {
+            match cmd {
+                Cmd::Mask(mask) => {
+                    mask_or = 0_u64;
+                    mask_and = u64::MAX;
+                    for (i, c) in mask.chars().enumerate() {
+                        let m = 1_u64 << (35 - i);
+                        match c {
+                            '0' => {
+                                mask_and &= !m;
+                            }
+                            '1' => {
+                                mask_or |= m;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Cmd::Mem(index, value0) => {
+                    let value = (value0 | mask_or) & mask_and;
+                    mem.insert(*index, value);
+                }
+            }
+        }
+        Ok(mem.values().sum())
+    }
+
+    fn part2(cmds: &Self::Input) -> Result<Self::Answer2> {
+        let mut masks_or: Vec<u128> = vec![];
+        let mut mask_and = u128::MAX;
+        let mut mem = BTreeMap::new();
+        for cmd in cmds {
+            match cmd {
+                Cmd::Mask(mask) => {
+                    masks_or = vec![0];
+                    mask_and = u128::MAX;
+                    let mut mask_or = 0_u128;
+                    for (i, c) in mask.chars().enumerate() {
+                        let m = 1_u128 << (35 - i);
+                        match c {
+                            '1' => {
+                                mask_or |= m;
+                            }
+                            'X' => {
+                                masks_or = masks_or
+                                    .iter()
+                                    .flat_map(|&mo| vec![mo, mo | m].into_iter())
+                                    .collect();
+                                mask_and &= !m;
+                            }
+                            _ => {}
+                        }
+                    }
+                    for m in &mut masks_or {
+                        *m |= mask_or;
+                    }
+                }
+                Cmd::Mem(index, value) => {
+                    for mo in &masks_or {
+                        let addr: u128 = (*index as u128 & mask_and) | mo;
+                        mem.insert(addr, *value as u128);
+                    }
+                }
+            }
+        }
+        Ok(mem.values().sum::<u128>())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<u64> {
+    Day14::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<u128> {
+    Day14::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] =
+        b"mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] = 101\nmem[8] = 0\n";
+    assert_eq!(solve_a(input)?, 165);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"mask = 000000000000000000000000000000X1001X\nmem[42] = 100\nmask = 00000000000000000000000000000000X0XX\nmem[26] = 1\n";
+    assert_eq!(solve_b(input)?, 208);
+    Ok(())
+}
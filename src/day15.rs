@@ -0,0 +1,214 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_list;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+// Process, etc //
+
+/// The numbers spoken in the memory game, starting with the given ones
+#[derive(Debug, Clone)]
+pub struct MemoryGame {
+    start: Vec<u32>,
+    /// The number of numbers spoken so far
+    turn: u32,
+    last: u32,
+    /// The turn each number was last spoken, not counting the last
+    /// number; 0 if never
+    seen: Vec<u32>,
+    /// Same as seen, for starting numbers too large for it; numbers
+    /// spoken after the start are never larger than the turn
+    large: HashMap<u32, u32>,
+}
+
+impl MemoryGame {
+    pub fn new(start: &[u32]) -> MemoryGame {
+        MemoryGame::with_capacity(start, 0)
+    }
+
+    /// Preallocate the table for the given number of turns: no number
+    /// spoken after the start can be larger than that
+    pub fn with_capacity(start: &[u32], turns: usize) -> MemoryGame {
+        MemoryGame {
+            start: start.to_vec(),
+            turn: 0,
+            last: 0,
+            seen: vec![0; turns],
+            large: HashMap::new(),
+        }
+    }
+
+    fn last_seen(&self, value: u32) -> u32 {
+        match self.seen.get(value as usize) {
+            Some(&turn) => turn,
+            None => self.large.get(&value).copied().unwrap_or(0),
+        }
+    }
+
+    fn set_seen(&mut self, value: u32, turn: u32) {
+        let index = value as usize;
+        if index >= self.seen.len() && value <= turn {
+            // Grow the table, taking the numbers it now covers from large
+            let len = (index + 1).max(2 * self.seen.len());
+            self.seen.resize(len, 0);
+            let seen = &mut self.seen;
+            self.large
+                .retain(|&v, &mut t| match seen.get_mut(v as usize) {
+                    Some(slot) => {
+                        *slot = t;
+                        false
+                    }
+                    None => true,
+                });
+        }
+        match self.seen.get_mut(index) {
+            Some(slot) => *slot = turn,
+            None => {
+                self.large.insert(value, turn);
+            }
+        }
+    }
+}
+
+impl Iterator for MemoryGame {
+    type Item = u32;
+    /// None if there are no starting numbers, or after u32::MAX turns
+    fn next(&mut self) -> Option<u32> {
+        if self.start.is_empty() {
+            return None;
+        }
+        let next_turn = self.turn.checked_add(1)?;
+        let value = match self.start.get(self.turn as usize) {
+            Some(&value) => value,
+            None => match self.last_seen(self.last) {
+                0 => 0,
+                before => self.turn - before,
+            },
+        };
+        if self.turn > 0 {
+            self.set_seen(self.last, self.turn);
+        }
+        self.last = value;
+        self.turn = next_turn;
+        Some(value)
+    }
+}
+
+/// The number spoken at the given turn, starting at 1
+pub fn spoken_at(start: &[u32], turn: usize) -> Option<u32> {
+    if turn > u32::MAX as usize {
+        return None;
+    }
+    MemoryGame::with_capacity(start, turn).nth(turn.checked_sub(1)?)
+}
+
+// Solver //
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut nums = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            nums.extend(parse_list::<u32>(15, i + 1, &line_opt?, ',')?);
+        }
+        Ok(nums)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<Self::Answer1> {
+        spoken_at(nums, 2020).ok_or_else(|| anyhow!("no starting numbers"))
+    }
+
+    fn part2(nums: &Self::Input) -> Result<Self::Answer2> {
+        spoken_at(nums, 30_000_000).ok_or_else(|| anyhow!("no starting numbers"))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<u32> {
+    Day15::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<u32> {
+    Day15::run2(bufin)
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    let input: &[u8] = b"0,3,6\n";
+    assert_eq!(solve_a(input)?, 436);
+    Ok(())
+}
+
+#[test]
+fn test2_a() -> Result<()> {
+    let input: &[u8] = b"1,3,2\n";
+    assert_eq!(solve_a(input)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test3_a() -> Result<()> {
+    let input: &[u8] = b"2,1,3\n";
+    assert_eq!(solve_a(input)?, 10);
+    Ok(())
+}
+
+#[test]
+fn test4_a() -> Result<()> {
+    let input: &[u8] = b"1,2,3\n";
+    assert_eq!(solve_a(input)?, 27);
+    Ok(())
+}
+
+#[test]
+fn test5_a() -> Result<()> {
+    let input: &[u8] = b"2,3,1\n";
+    assert_eq!(solve_a(input)?, 78);
+    Ok(())
+}
+
+#[test]
+fn test6_a() -> Result<()> {
+    let input: &[u8] = b"3,2,1\n";
+    assert_eq!(solve_a(input)?, 438);
+    Ok(())
+}
+
+#[test]
+fn test7_a() -> Result<()> {
+    let input: &[u8] = b"3,1,2\n";
+    assert_eq!(solve_a(input)?, 1836);
+    Ok(())
+}
+
+#[test]
+fn test_memory_game() {
+    let spoken = MemoryGame::new(&[0, 3, 6]).take(10).collect::<Vec<_>>();
+    assert_eq!(spoken, vec![0, 3, 6, 0, 3, 3, 1, 0, 4, 0]);
+    assert_eq!(spoken_at(&[0, 3, 6], 2020), Some(436));
+    assert_eq!(spoken_at(&[0, 3, 6], 2), Some(3));
+    assert_eq!(spoken_at(&[0, 3, 6], 0), None);
+    assert_eq!(spoken_at(&[], 1), None);
+    // Large starting numbers don't get a table entry each
+    let start = [4_000_000_000, 3, 4_000_000_000];
+    let spoken = MemoryGame::with_capacity(&start, 10).take(6);
+    assert_eq!(
+        spoken.collect::<Vec<_>>(),
+        vec![4_000_000_000, 3, 4_000_000_000, 2, 0, 0]
+    );
+    assert_eq!(spoken_at(&[4_000_000_000, 0], 4), Some(1));
+    // Turns past u32::MAX can't be counted
+    assert_eq!(spoken_at(&[0, 3, 6], u32::MAX as usize + 1), None);
+    let mut game = MemoryGame::new(&[0, 3, 6]);
+    game.turn = u32::MAX - 1;
+    assert!(game.next().is_some());
+    assert_eq!(game.next(), None);
+}
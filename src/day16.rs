@@ -0,0 +1,453 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_list;
+use crate::interval::IntervalSet;
+use crate::matching::{assignments, is_unique, matching};
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use log::{debug, info, trace};
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+
+/// Get the next line along with its number
+pub fn line_next(
+    lines_iter: &mut impl Iterator<Item = (usize, io::Result<String>)>,
+) -> Result<(usize, String)> {
+    let (i, line) = lines_iter
+        .next()
+        .ok_or_else(|| anyhow!("error reading line"))?;
+    Ok((i + 1, line?))
+}
+
+/// Check that the next line is exactly `expected`
+pub fn line_expect(
+    lines_iter: &mut impl Iterator<Item = (usize, io::Result<String>)>,
+    expected: &str,
+) -> Result<()> {
+    let (lineno, line) = line_next(lines_iter)?;
+    if line != expected {
+        let expected = format!("{:?}", expected);
+        return Err(ParseError::new(16, lineno, 1, &line, &expected).into());
+    }
+    Ok(())
+}
+
+/// Parse a ticket, which must have a value for each field
+pub fn parse_ticket(num_fields: usize, lineno: usize, line: &str) -> Result<Vec<i64>> {
+    let ticket = parse_list(16, lineno, line, ',')?;
+    if ticket.len() != num_fields {
+        let expected = format!("{} values", num_fields);
+        return Err(ParseError::new(16, lineno, 1, line, &expected).into());
+    }
+    Ok(ticket)
+}
+
+// Notes //
+
+/// A field rule: a name and the values it accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub ranges: IntervalSet,
+}
+
+impl Rule {
+    pub fn contains(&self, num: i64) -> bool {
+        self.ranges.contains(num)
+    }
+}
+
+// Parser: //
+
+/// Rules look like "name: 1-3 or 5 or >= 10"; each alternative is a
+/// range, a single value or a comparison with a value (">=", ">", "<=" or
+/// "<")
+pub mod parser {
+    use super::Rule;
+    use crate::interval::IntervalSet;
+    use nom::{
+        branch::alt, bytes::complete::tag, bytes::complete::take_till1, character::complete::char,
+        character::complete::space0, character::complete::u64 as number, combinator::all_consuming,
+        combinator::map, combinator::map_opt, combinator::map_res, combinator::opt, error::Error,
+        error::ErrorKind, multi::separated_list1, sequence::pair, sequence::preceded, IResult,
+    };
+
+    pub fn value(input: &str) -> IResult<&str, i64> {
+        map_res(number, i64::try_from)(input)
+    }
+
+    fn compare<'a>(op: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, i64> {
+        preceded(pair(tag(op), space0), value)
+    }
+
+    pub fn range(input: &str) -> IResult<&str, (i64, i64)> {
+        alt((
+            map(compare(">="), |lo| (lo, i64::MAX)),
+            map_opt(compare(">"), |lo| Some((lo.checked_add(1)?, i64::MAX))),
+            map(compare("<="), |hi| (i64::MIN, hi)),
+            map(compare("<"), |hi| (i64::MIN, hi - 1)),
+            span,
+        ))(input)
+    }
+
+    /// "lo-hi" or a single value; a reversed range is a failure, so that it
+    /// is reported instead of ending the list of ranges
+    fn span(input: &str) -> IResult<&str, (i64, i64)> {
+        let (rest, (lo, hi)) = pair(value, opt(preceded(char('-'), value)))(input)?;
+        let hi = hi.unwrap_or(lo);
+        if lo > hi {
+            return Err(nom::Err::Failure(Error::new(input, ErrorKind::Verify)));
+        }
+        Ok((rest, (lo, hi)))
+    }
+
+    pub fn ranges(input: &str) -> IResult<&str, IntervalSet> {
+        map(separated_list1(tag(" or "), range), |r| {
+            r.into_iter().collect()
+        })(input)
+    }
+
+    pub fn rule(input: &str) -> IResult<&str, Rule> {
+        let (input, name) = take_till1(|c| c == ':')(input)?;
+        let (input, _) = tag(": ")(input)?;
+        let (input, ranges) = ranges(input)?;
+        Ok((
+            input,
+            Rule {
+                name: name.to_string(),
+                ranges,
+            },
+        ))
+    }
+
+    pub fn rule_line(input: &str) -> IResult<&str, Rule> {
+        all_consuming(rule)(input)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TicketNotes {
+    pub rules: Vec<Rule>,
+    pub myticket: Vec<i64>,
+    pub nearby: Vec<Vec<i64>>,
+}
+
+/// The result of deducing which field is at each position of the tickets;
+/// assignments have the index of the rule of each position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Deduction {
+    Unique(Vec<usize>),
+    /// All the valid assignments, up to MAX_ASSIGNMENTS of them
+    Ambiguous(Vec<Vec<usize>>),
+    Conflict(Conflict),
+}
+
+/// A set of positions that can't all get a field, because together they
+/// fit fewer fields than there are positions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub positions: Vec<usize>,
+    pub fields: Vec<String>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let positions = self.positions.iter().map(|p| p.to_string());
+        write!(f, "positions {} ", positions.collect::<Vec<_>>().join(", "))?;
+        if self.fields.is_empty() {
+            write!(f, "fit no field")
+        } else {
+            write!(f, "only fit the fields {}", self.fields.join(", "))
+        }
+    }
+}
+
+pub const MAX_ASSIGNMENTS: usize = 1000;
+
+impl TicketNotes {
+    pub fn is_valid(&self, ticket: &[i64]) -> bool {
+        ticket
+            .iter()
+            .all(|&num| self.rules.iter().any(|r| r.contains(num)))
+    }
+
+    /// For each position, the rules that fit all valid nearby tickets
+    pub fn candidates(&self) -> Vec<Vec<usize>> {
+        let valid = self
+            .nearby
+            .iter()
+            .filter(|t| self.is_valid(t))
+            .collect::<Vec<_>>();
+        (0..self.rules.len())
+            .map(|ipos| {
+                let cands = (0..self.rules.len())
+                    .filter(|&irule| valid.iter().all(|t| self.rules[irule].contains(t[ipos])))
+                    .collect::<Vec<_>>();
+                trace!("position {} fits rules {:?}", ipos, cands);
+                cands
+            })
+            .collect()
+    }
+
+    /// Find which rule is at each position: a perfect bipartite matching
+    /// between positions and rules
+    pub fn deduce(&self) -> Deduction {
+        let cands = self.candidates();
+        let assignment = match matching(&cands, self.rules.len()) {
+            Ok(assignment) => assignment,
+            Err(positions) => {
+                let mut fields = positions
+                    .iter()
+                    .flat_map(|&p| cands[p].iter())
+                    .copied()
+                    .collect::<Vec<_>>();
+                fields.sort_unstable();
+                fields.dedup();
+                return Deduction::Conflict(Conflict {
+                    positions,
+                    fields: fields
+                        .into_iter()
+                        .map(|r| self.rules[r].name.clone())
+                        .collect(),
+                });
+            }
+        };
+        if is_unique(&cands, self.rules.len(), &assignment) {
+            return Deduction::Unique(assignment);
+        }
+        // Only listed for the error message
+        let all = assignments(&cands, MAX_ASSIGNMENTS);
+        debug!("found {} assignment(s)", all.len());
+        Deduction::Ambiguous(all)
+    }
+}
+
+// Solver //
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = TicketNotes;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut lines_iter = bufin.lines().enumerate();
+        let mut notes = TicketNotes::default();
+        loop {
+            let (lineno, line) = line_next(&mut lines_iter)?;
+            if line.is_empty() {
+                break;
+            }
+            let (_, rule) = parser::rule_line(&line)
+                .map_err(|e| ParseError::from_nom(16, &line, e).at_line(lineno))?;
+            notes.rules.push(rule);
+        }
+        line_expect(&mut lines_iter, "your ticket:")?;
+        let (lineno, line) = line_next(&mut lines_iter)?;
+        notes.myticket = parse_ticket(notes.rules.len(), lineno, &line)?;
+        line_expect(&mut lines_iter, "")?;
+        line_expect(&mut lines_iter, "nearby tickets:")?;
+        for (i, line_opt) in lines_iter {
+            let line = line_opt?;
+            notes
+                .nearby
+                .push(parse_ticket(notes.rules.len(), i + 1, &line)?);
+        }
+        Ok(notes)
+    }
+
+    fn part1(notes: &Self::Input) -> Result<Self::Answer1> {
+        let mut invalid = 0;
+        for &num in notes.nearby.iter().flatten() {
+            if !notes.rules.iter().any(|r| r.contains(num)) {
+                invalid += num;
+            }
+        }
+        Ok(invalid)
+    }
+
+    fn part2(notes: &Self::Input) -> Result<Self::Answer2> {
+        let assignment = match notes.deduce() {
+            Deduction::Unique(assignment) => assignment,
+            Deduction::Ambiguous(all) => {
+                return Err(anyhow!(
+                    "ambiguous ticket fields, {}{} possible assignments",
+                    all.len(),
+                    if all.len() == MAX_ASSIGNMENTS {
+                        "+"
+                    } else {
+                        ""
+                    }
+                ))
+            }
+            Deduction::Conflict(conflict) => return Err(anyhow!("{}", conflict)),
+        };
+        let mut ret = 1;
+        for (&irule, v) in assignment.iter().zip(&notes.myticket) {
+            let name = &notes.rules[irule].name;
+            info!("myticket {} = {}", name, v);
+            if name.starts_with("departure") {
+                ret *= v;
+            }
+        }
+        Ok(ret)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day16::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day16::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b"class: 1-3 or 5-7\nrow: 6-11 or 33-44\nseat: 13-40 or 45-50\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,3,47\n40,4,50\n55,2,20\n38,6,12\n";
+    assert_eq!(solve_a(input)?, 71);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"class: 0-1 or 4-19\nrow: 0-5 or 8-19\nseat: 0-13 or 16-19\n\nyour ticket:\n11,12,13\n\nnearby tickets:\n3,9,18\n15,1,5\n5,14,9";
+    assert_eq!(solve_b(input)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_deduce() -> Result<()> {
+    let notes = |nearby: &[u8]| -> Result<TicketNotes> {
+        let mut input = b"a: 0-1 or 4-19\nb: 0-5 or 8-19\nc: 0-13 or 16-19\n\nyour ticket:\n11,12,13\n\nnearby tickets:\n".to_vec();
+        input.extend_from_slice(nearby);
+        Day16::parse(&input[..])
+    };
+    assert_eq!(
+        notes(b"3,9,18\n15,1,5\n5,14,9\n")?.deduce(),
+        Deduction::Unique(vec![1, 0, 2])
+    );
+    let Deduction::Ambiguous(all) = notes(b"3,9,18\n")?.deduce() else {
+        panic!("expected ambiguous");
+    };
+    assert_eq!(
+        all,
+        vec![vec![1, 0, 2], vec![1, 2, 0], vec![2, 0, 1], vec![2, 1, 0]]
+    );
+    // 14 doesn't fit c and 6 doesn't fit b, so the first two positions
+    // can only be a
+    let Deduction::Conflict(conflict) = notes(b"14,14,3\n6,6,3\n")?.deduce() else {
+        panic!("expected conflict");
+    };
+    assert_eq!(conflict.positions, vec![0, 1]);
+    assert_eq!(conflict.fields, vec!["a"]);
+    assert_eq!(conflict.to_string(), "positions 0, 1 only fit the fields a");
+    assert!(solve_b(
+        &b"a: 1-1 or 3-3\nb: 1-1 or 4-4\n\nyour ticket:\n1,1\n\nnearby tickets:\n3,3\n"[..]
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_many_fields() -> Result<()> {
+    // Field i accepts values up to i, so position p, which has the value
+    // p in every ticket, can only be the fields from p on; the only
+    // assignment is the reverse order
+    let n = 100;
+    let mut input = String::new();
+    for i in 0..n {
+        input.push_str(&format!("departure{}: 0-{} or 1000-1000\n", i, n - 1 - i));
+    }
+    let ticket = (0..n).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+    input.push_str(&format!(
+        "\nyour ticket:\n{}\n\nnearby tickets:\n{}\n",
+        ticket, ticket
+    ));
+    let notes = Day16::parse(input.as_bytes())?;
+    assert_eq!(
+        notes.deduce(),
+        Deduction::Unique((0..n).rev().collect::<Vec<_>>())
+    );
+    // Every position fits every field: deciding that it is ambiguous
+    // doesn't depend on the number of assignments
+    let mut input = String::new();
+    for i in 0..n {
+        input.push_str(&format!("field{}: 0-{}\n", i, n));
+    }
+    input.push_str(&format!(
+        "\nyour ticket:\n{}\n\nnearby tickets:\n{}\n",
+        ticket, ticket
+    ));
+    let notes = Day16::parse(input.as_bytes())?;
+    let Deduction::Ambiguous(all) = notes.deduce() else {
+        panic!("expected ambiguous");
+    };
+    assert_eq!(all.len(), MAX_ASSIGNMENTS);
+    assert!(Day16::part2(&notes).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parser_rule() -> Result<()> {
+    let (_, rule) = parser::rule_line("arrival row: 1-3 or 5 or 7-9 or >= 20")?;
+    assert_eq!(rule.name, "arrival row");
+    assert_eq!(
+        rule.ranges.ranges(),
+        &[(1, 3), (5, 5), (7, 9), (20, i64::MAX)]
+    );
+    assert!(rule.contains(5) && rule.contains(1000) && !rule.contains(4));
+    let (_, rule) = parser::rule_line("x: <= 3 or 2-4 or 10 or >11")?;
+    assert_eq!(
+        rule.ranges.ranges(),
+        &[(i64::MIN, 4), (10, 10), (12, i64::MAX)]
+    );
+    let (_, rule) = parser::rule_line("x: < 3 or > 5")?;
+    assert_eq!(rule.ranges.ranges(), &[(i64::MIN, 2), (6, i64::MAX)]);
+    assert!(parser::rule_line("x: 1-3 or").is_err());
+    assert!(parser::rule_line("x 1-3").is_err());
+    assert!(parser::rule_line("x: -3").is_err());
+    assert!(parser::rule_line("x: 9223372036854775808").is_err());
+    let input: &[u8] = b"a: 1-3 or 5 or 7-9\nb: 4 or 6 or >= 12\n\nyour ticket:\n4,1\n\nnearby tickets:\n1,12\n11,5\n";
+    assert_eq!(solve_a(input)?, 11);
+    let err = Day16::parse(&b"a: 1-3 or x\n"[..]).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (1, 7));
+    // Reversed ranges are errors, not empty ranges
+    let err = Day16::parse(&b"a: 1-3 or 5-3\n"[..]).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (1, 11));
+    assert_eq!(err.expected, "a valid value");
+    Ok(())
+}
+
+#[test]
+fn test_display_parse() -> Result<()> {
+    use crate::interval::IntervalSet;
+    use nom::combinator::all_consuming;
+    let sets = [
+        vec![(1, 3), (5, 5), (7, 9), (20, i64::MAX)],
+        vec![(i64::MIN, 4), (10, 10), (12, i64::MAX)],
+        vec![(0, 0), (2, 2)],
+        vec![(i64::MIN, i64::MAX)],
+    ];
+    for ranges in sets {
+        let set = ranges.into_iter().collect::<IntervalSet>();
+        let text = set.to_string();
+        let parsed = text
+            .split(" or ")
+            .map(|r| {
+                all_consuming(parser::range)(r)
+                    .map(|(_, range)| range)
+                    .map_err(|e| ParseError::from_nom(16, r, e))
+            })
+            .collect::<Result<IntervalSet, _>>()?;
+        assert_eq!(parsed, set, "{}", text);
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::conway::{Conway, Rules};
+use crate::grid::Grid;
+use crate::Solver;
+use anyhow::Result;
+use std::io::BufRead;
+
+/// Number of active cubes after 6 cycles in a space with N dimensions
+pub fn boot<const N: usize>(active: &[(i32, i32)]) -> Result<usize> {
+    let mut conway = Conway::<N>::from_plane(active, Rules::default())?;
+    Ok(*conway.run(6).last().unwrap())
+}
+
+// Solver //
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    /// The x, y coordinates of the initially active cubes
+    type Input = Vec<(i32, i32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let grid = Grid::parse(17, bufin, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(grid
+            .iter()
+            .filter(|(_, &active)| active)
+            .map(|(xy, _)| (xy.x, xy.y))
+            .collect())
+    }
+
+    fn part1(active: &Self::Input) -> Result<Self::Answer1> {
+        boot::<3>(active)
+    }
+
+    fn part2(active: &Self::Input) -> Result<Self::Answer2> {
+        boot::<4>(active)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day17::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day17::run2(bufin)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let input: &[u8] = b".#.\n..#\n###\n";
+    assert_eq!(solve_a(input)?, 112);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b".#.\n..#\n###\n";
+    assert_eq!(solve_b(input)?, 848);
+    Ok(())
+}
@@ -0,0 +1,626 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, bail, Result};
+use nom::branch::alt;
+use nom::character::complete::{char, digit1, one_of};
+use nom::combinator::map_res;
+use nom::multi::{many0, many1};
+use nom::IResult;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// Tokens //
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Token {
+    Num(i64),
+    Plus,
+    Minus,
+    Mult,
+    Div,
+    ParOp,
+    ParCl,
+}
+
+fn num(input: &str) -> IResult<&str, Token> {
+    let (input, _) = many0(char(' '))(input)?;
+    map_res(digit1, |n: &str| n.parse::<i64>().map(Token::Num))(input)
+}
+
+fn op(input: &str) -> IResult<&str, Token> {
+    let (input, _) = many0(char(' '))(input)?;
+    let (input, c) = one_of("+-*/()")(input)?;
+    let token = match c {
+        '+' => Token::Plus,
+        '-' => Token::Minus,
+        '*' => Token::Mult,
+        '/' => Token::Div,
+        '(' => Token::ParOp,
+        _ => Token::ParCl,
+    };
+    Ok((input, token))
+}
+
+/// Tokens with the byte offset where each one starts
+pub fn parse_spanned(input: &str) -> IResult<&str, Vec<(usize, Token)>> {
+    many1(|i| -> IResult<&str, (usize, Token)> {
+        let (i, _) = many0(char(' '))(i)?;
+        let offset = input.len() - i.len();
+        let (i, token) = alt((num, op))(i)?;
+        Ok((i, (offset, token)))
+    })(input)
+}
+
+pub fn parse_tokens(input: &str) -> IResult<&str, Vec<Token>> {
+    let (input, tokens) = parse_spanned(input)?;
+    Ok((input, tokens.into_iter().map(|(_, t)| t).collect()))
+}
+
+/// The tokens of a whole line, which is kept for error messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenLine {
+    pub text: String,
+    pub tokens: Vec<(usize, Token)>,
+}
+
+impl FromStr for TokenLine {
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (rest, tokens) = parse_spanned(line).map_err(|e| ParseError::from_nom(18, line, e))?;
+        if !rest.trim().is_empty() {
+            let expected = "a number, operator or parenthesis";
+            return Err(ParseError::at_rest(18, line, rest.trim_start(), expected));
+        }
+        Ok(TokenLine {
+            text: line.to_string(),
+            tokens,
+        })
+    }
+}
+
+// Precedence //
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinOp {
+    fn from_token(token: Token) -> Option<BinOp> {
+        match token {
+            Token::Plus => Some(BinOp::Add),
+            Token::Minus => Some(BinOp::Sub),
+            Token::Mult => Some(BinOp::Mul),
+            Token::Div => Some(BinOp::Div),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+/// Precedence and associativity of each binary operator; higher
+/// precedences bind tighter. Unary minus binds tighter than all of them.
+#[derive(Debug, Clone)]
+pub struct Table {
+    ops: BTreeMap<BinOp, (u32, Assoc)>,
+}
+
+impl Table {
+    pub fn new(ops: &[(BinOp, u32, Assoc)]) -> Table {
+        Table {
+            ops: ops.iter().map(|&(op, p, a)| (op, (p, a))).collect(),
+        }
+    }
+
+    /// Part 1: everything left to right
+    pub fn same() -> Table {
+        Table::new(&[
+            (BinOp::Add, 1, Assoc::Left),
+            (BinOp::Sub, 1, Assoc::Left),
+            (BinOp::Mul, 1, Assoc::Left),
+            (BinOp::Div, 1, Assoc::Left),
+        ])
+    }
+
+    /// Part 2: additions and subtractions before multiplications and
+    /// divisions
+    pub fn plus_first() -> Table {
+        Table::new(&[
+            (BinOp::Add, 2, Assoc::Left),
+            (BinOp::Sub, 2, Assoc::Left),
+            (BinOp::Mul, 1, Assoc::Left),
+            (BinOp::Div, 1, Assoc::Left),
+        ])
+    }
+
+    /// The usual math rules
+    pub fn standard() -> Table {
+        Table::new(&[
+            (BinOp::Add, 1, Assoc::Left),
+            (BinOp::Sub, 1, Assoc::Left),
+            (BinOp::Mul, 2, Assoc::Left),
+            (BinOp::Div, 2, Assoc::Left),
+        ])
+    }
+
+    pub fn get(&self, op: BinOp) -> Option<(u32, Assoc)> {
+        self.ops.get(&op).copied()
+    }
+}
+
+// Expr //
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Expr {
+    Num(i64),
+    Neg(Box<Expr>),
+    Bin(BinOp, Box<Expr>, Box<Expr>),
+}
+
+/// Fully parenthesized, to make the precedence visible
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Neg(e) => write!(f, "-{}", e),
+            Expr::Bin(op, a, b) => write!(f, "({} {} {})", a, op, b),
+        }
+    }
+}
+
+/// Integer types that expressions can be evaluated with
+pub trait Number: Copy + PartialEq + From<i64> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! number {
+    ($t:ty) => {
+        impl Number for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+            fn checked_neg(self) -> Option<Self> {
+                <$t>::checked_neg(self)
+            }
+        }
+    };
+}
+
+number!(i64);
+number!(i128);
+
+impl Expr {
+    /// Parse a whole line with the given precedences
+    pub fn parse(line: &str, table: &Table) -> Result<Expr, ParseError> {
+        Expr::from_tokens(&line.parse::<TokenLine>()?, table)
+    }
+
+    /// Build the expression of an already tokenized line
+    pub fn from_tokens(line: &TokenLine, table: &Table) -> Result<Expr, ParseError> {
+        let mut parser = Parser {
+            line: &line.text,
+            tokens: &line.tokens,
+            pos: 0,
+            table,
+        };
+        let expr = parser.expr(0)?;
+        if parser.pos < parser.tokens.len() {
+            return Err(parser.error("an operator"));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluate with integer division, failing on overflows and divisions
+    /// by zero
+    pub fn eval<N: Number>(&self) -> Result<N> {
+        Ok(match self {
+            Expr::Num(n) => N::from(*n),
+            Expr::Neg(e) => e
+                .eval::<N>()?
+                .checked_neg()
+                .ok_or_else(|| anyhow!("overflow in {}", self))?,
+            Expr::Bin(op, a, b) => {
+                let (a, b) = (a.eval::<N>()?, b.eval::<N>()?);
+                if *op == BinOp::Div && b == N::from(0) {
+                    bail!("division by zero in {}", self);
+                }
+                match op {
+                    BinOp::Add => a.checked_add(b),
+                    BinOp::Sub => a.checked_sub(b),
+                    BinOp::Mul => a.checked_mul(b),
+                    BinOp::Div => a.checked_div(b),
+                }
+                .ok_or_else(|| anyhow!("overflow in {}", self))?
+            }
+        })
+    }
+}
+
+/// Pratt parser over the tokens of a line
+struct Parser<'a> {
+    line: &'a str,
+    tokens: &'a [(usize, Token)],
+    pos: usize,
+    table: &'a Table,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).map(|&(_, t)| t)
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        let offset = self.tokens.get(self.pos).map_or(self.line.len(), |t| t.0);
+        ParseError::at_offset(18, self.line, offset, expected)
+    }
+
+    /// Parse operators with at least the given precedence
+    fn expr(&mut self, min_prec: u32) -> Result<Expr, ParseError> {
+        let mut lhs = self.primary()?;
+        while let Some(op) = self.peek().and_then(BinOp::from_token) {
+            let (prec, assoc) = self
+                .table
+                .get(op)
+                .ok_or_else(|| self.error("a supported operator"))?;
+            if prec < min_prec {
+                break;
+            }
+            self.pos += 1;
+            let next_min = match assoc {
+                Assoc::Left => prec + 1,
+                Assoc::Right => prec,
+            };
+            let rhs = self.expr(next_min)?;
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let token = self
+            .peek()
+            .ok_or_else(|| self.error("a number or parenthesis"))?;
+        match token {
+            Token::Num(n) => {
+                self.pos += 1;
+                Ok(Expr::Num(n))
+            }
+            Token::Minus => {
+                self.pos += 1;
+                Ok(Expr::Neg(Box::new(self.primary()?)))
+            }
+            Token::ParOp => {
+                self.pos += 1;
+                let expr = self.expr(0)?;
+                if self.peek() != Some(Token::ParCl) {
+                    return Err(self.error("a closing parenthesis"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            _ => Err(self.error("a number or parenthesis")),
+        }
+    }
+}
+
+/// Sum of the lines evaluated with the given precedences
+pub fn sum_lines<N: Number>(lines: &[TokenLine], table: &Table) -> Result<N> {
+    let mut sum = N::from(0);
+    for (i, line) in lines.iter().enumerate() {
+        let expr = Expr::from_tokens(line, table).map_err(|e| e.at_line(i + 1))?;
+        let value = expr.eval::<N>()?;
+        sum = sum
+            .checked_add(value)
+            .ok_or_else(|| anyhow!("overflow in the sum"))?;
+    }
+    Ok(sum)
+}
+
+// Solver //
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    /// The expressions, already checked for syntax errors
+    type Input = Vec<TokenLine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut lines = vec![];
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            lines.push(line.parse::<TokenLine>().map_err(|e| e.at_line(i + 1))?);
+        }
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Self::Answer1> {
+        sum_lines(lines, &Table::same())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Self::Answer2> {
+        sum_lines(lines, &Table::plus_first())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day18::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day18::run2(bufin)
+}
+
+#[test]
+fn test_parse_error() {
+    let input: &[u8] = b"1 + 2\n3 * x\n";
+    let err = Day18::parse(input).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "3 * x"));
+    // Errors in the structure show up when the expression is built
+    let lines = Day18::parse(&b"1 + 2\n(3 * 4\n"[..]).unwrap();
+    let err = Day18::part1(&lines).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (2, 7));
+}
+
+#[test]
+fn test_parser1() -> Result<()> {
+    assert_eq!(num("2345asdf")?, ("asdf", Token::Num(2345)));
+    Ok(())
+}
+
+#[test]
+fn test_parser2() -> Result<()> {
+    assert_eq!(
+        parse_tokens("1+2*3+(4*5)+8")?,
+        (
+            "",
+            vec![
+                Token::Num(1),
+                Token::Plus,
+                Token::Num(2),
+                Token::Mult,
+                Token::Num(3),
+                Token::Plus,
+                Token::ParOp,
+                Token::Num(4),
+                Token::Mult,
+                Token::Num(5),
+                Token::ParCl,
+                Token::Plus,
+                Token::Num(8)
+            ]
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parser3() -> Result<()> {
+    assert_eq!(
+        parse_tokens("1 +  2 * 3+(    4 *5)+  8")?,
+        (
+            "",
+            vec![
+                Token::Num(1),
+                Token::Plus,
+                Token::Num(2),
+                Token::Mult,
+                Token::Num(3),
+                Token::Plus,
+                Token::ParOp,
+                Token::Num(4),
+                Token::Mult,
+                Token::Num(5),
+                Token::ParCl,
+                Token::Plus,
+                Token::Num(8)
+            ]
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    let input: &[u8] = b"1 + 2 * 3 + 4 * 5 + 6\n";
+    assert_eq!(solve_a(input)?, 71);
+    Ok(())
+}
+
+#[test]
+fn test2_a() -> Result<()> {
+    let input: &[u8] = b"1 + (2 * 3) + (4 * (5 + 6))\n";
+    assert_eq!(solve_a(input)?, 51);
+    Ok(())
+}
+
+#[test]
+fn test3_a() -> Result<()> {
+    let input: &[u8] = b"2 * 3 + (4 * 5)\n";
+    assert_eq!(solve_a(input)?, 26);
+    Ok(())
+}
+
+#[test]
+fn test4_a() -> Result<()> {
+    let input: &[u8] = b"5 + (8 * 3 + 9 + 3 * 4 * 3)\n";
+    assert_eq!(solve_a(input)?, 437);
+    Ok(())
+}
+
+#[test]
+fn test5_a() -> Result<()> {
+    let input: &[u8] = b"5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))\n";
+    assert_eq!(solve_a(input)?, 12240);
+    Ok(())
+}
+
+#[test]
+fn test6_a() -> Result<()> {
+    let input: &[u8] = b"((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2\n";
+    assert_eq!(solve_a(input)?, 13632);
+    Ok(())
+}
+
+#[test]
+fn test7_a() -> Result<()> {
+    let input: &[u8] = b"1 + (2 * 3)\n";
+    assert_eq!(solve_a(input)?, 7);
+    Ok(())
+}
+
+#[test]
+fn test1_b() -> Result<()> {
+    let input: &[u8] = b"1 + 2 * 3 + 4 * 5 + 6\n";
+    assert_eq!(solve_b(input)?, 231);
+    Ok(())
+}
+
+#[test]
+fn test2_b() -> Result<()> {
+    let input: &[u8] = b"1 + (2 * 3) + (4 * (5 + 6))\n";
+    assert_eq!(solve_b(input)?, 51);
+    Ok(())
+}
+
+#[test]
+fn test3_b() -> Result<()> {
+    let input: &[u8] = b"2 * 3 + (4 * 5)\n";
+    assert_eq!(solve_b(input)?, 46);
+    Ok(())
+}
+
+#[test]
+fn test4_b() -> Result<()> {
+    let input: &[u8] = b"5 + (8 * 3 + 9 + 3 * 4 * 3)\n";
+    assert_eq!(solve_b(input)?, 1445);
+    Ok(())
+}
+
+#[test]
+fn test5_b() -> Result<()> {
+    let input: &[u8] = b"5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))\n";
+    assert_eq!(solve_b(input)?, 669060);
+    Ok(())
+}
+
+#[test]
+fn test6_b() -> Result<()> {
+    let input: &[u8] = b"((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2\n";
+    assert_eq!(solve_b(input)?, 23340);
+    Ok(())
+}
+
+#[test]
+fn test7_b() -> Result<()> {
+    let input: &[u8] = b"1 + (2 * 3)\n";
+    assert_eq!(solve_b(input)?, 7);
+    Ok(())
+}
+
+#[test]
+fn test_precedence() -> Result<()> {
+    let line = "1 + 2 * 3 - 4 / 2";
+    assert_eq!(
+        Expr::parse(line, &Table::same())?.to_string(),
+        "((((1 + 2) * 3) - 4) / 2)"
+    );
+    assert_eq!(
+        Expr::parse(line, &Table::plus_first())?.to_string(),
+        "(((1 + 2) * (3 - 4)) / 2)"
+    );
+    assert_eq!(
+        Expr::parse(line, &Table::standard())?.to_string(),
+        "((1 + (2 * 3)) - (4 / 2))"
+    );
+    let right = Table::new(&[(BinOp::Sub, 1, Assoc::Right)]);
+    assert_eq!(
+        Expr::parse("8 - 4 - 2", &right)?.eval::<i64>()?,
+        8 - (4 - 2)
+    );
+    assert!(Expr::parse("8 * 4", &right).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_unary_div() -> Result<()> {
+    let table = Table::standard();
+    let eval = |line: &str| Expr::parse(line, &table)?.eval::<i64>();
+    assert_eq!(eval("-2 * 3")?, -6);
+    assert_eq!(eval("2 - -3")?, 5);
+    assert_eq!(eval("-(1 + 2) * -(3)")?, 9);
+    assert_eq!(eval("7 / 2 - 9 / -2")?, 7);
+    assert_eq!(
+        eval("1 / (2 - 2)").unwrap_err().to_string(),
+        "division by zero in (1 / (2 - 2))"
+    );
+    let err = Expr::parse("(1 + 2", &table).unwrap_err();
+    assert_eq!(
+        (err.column, err.expected.as_str()),
+        (7, "a closing parenthesis")
+    );
+    let err = Expr::parse("1 + * 2", &table).unwrap_err();
+    assert_eq!(
+        (err.column, err.expected.as_str()),
+        (5, "a number or parenthesis")
+    );
+    let err = Expr::parse("1 2", &table).unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (3, "an operator"));
+    Ok(())
+}
+
+#[test]
+fn test_overflow() -> Result<()> {
+    let expr = Expr::parse("3037000500 * 3037000500", &Table::same())?;
+    assert!(expr.eval::<i64>().is_err());
+    assert_eq!(expr.eval::<i128>()?, 3037000500i128 * 3037000500);
+    let expr = Expr::parse("-9223372036854775807 - 1", &Table::same())?;
+    assert_eq!(expr.eval::<i64>()?, i64::MIN);
+    assert!(Expr::parse("-(9223372036854775807 - -1)", &Table::same())?
+        .eval::<i64>()
+        .is_err());
+    assert!(Expr::parse("99999999999999999999", &Table::same()).is_err());
+    Ok(())
+}
@@ -0,0 +1,454 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::error::parse_line;
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, ensure, Result};
+use log::{debug, trace};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub fn line_next(lines_iter: &mut std::io::Lines<impl BufRead>) -> Result<String> {
+    lines_iter
+        .next()
+        .ok_or_else(|| anyhow!("error reading line"))?
+        .map_err(|e| anyhow!(e))
+}
+
+// Grammar //
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    Char(char),
+    /// Alternatives, each one a sequence of rule ids
+    Alts(Vec<Vec<usize>>),
+}
+
+/// Parse the contents of a rule, after the "<id>: "
+impl FromStr for Rule {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = s.chars().collect::<Vec<_>>();
+        if let ['"', c, '"'] = chars[..] {
+            return Ok(Rule::Char(c));
+        }
+        let mut alts = vec![vec![]];
+        let mut offset = 0;
+        for token in s.split(' ') {
+            if token == "|" && !alts.last().unwrap().is_empty() {
+                alts.push(vec![]);
+            } else {
+                let id = token
+                    .parse()
+                    .map_err(|_| ParseError::at_offset(19, s, offset, "a rule id"))?;
+                alts.last_mut().unwrap().push(id);
+            }
+            offset += token.len() + 1;
+        }
+        if alts.last().unwrap().is_empty() {
+            // Trailing "|", point past it
+            return Err(ParseError::at_offset(19, s, s.len(), "a rule id"));
+        }
+        Ok(Rule::Alts(alts))
+    }
+}
+
+/// Rules by id, all of them referring only to rules that exist
+#[derive(Debug, Default, Clone)]
+pub struct Grammar {
+    rules: BTreeMap<usize, Rule>,
+}
+
+impl Grammar {
+    pub fn new(rules: BTreeMap<usize, Rule>) -> Result<Grammar> {
+        let grammar = Grammar { rules };
+        for id in grammar.rules.keys() {
+            grammar.check(*id)?;
+        }
+        Ok(grammar)
+    }
+
+    /// Add or replace a rule
+    pub fn insert(&mut self, id: usize, rule: Rule) -> Result<()> {
+        let old = self.rules.insert(id, rule);
+        if let Err(e) = self.check(id) {
+            match old {
+                Some(old) => self.rules.insert(id, old),
+                None => self.rules.remove(&id),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn check(&self, id: usize) -> Result<()> {
+        if let Some(Rule::Alts(alts)) = self.rules.get(&id) {
+            for sub in alts.iter().flatten() {
+                ensure!(
+                    self.rules.contains_key(sub),
+                    "rule {} refers to missing rule {}",
+                    id,
+                    sub
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Rule> {
+        self.rules.get(&id)
+    }
+
+    /// Whether the whole message matches the rule
+    pub fn matches(&self, id: usize, message: &str) -> bool {
+        let mut matcher = Matcher::new(self, message);
+        matcher.run(id);
+        matcher.ends(id, 0).contains(&matcher.message.len())
+    }
+
+    /// How the whole message matches the rule, if it does
+    pub fn parse(&self, id: usize, message: &str) -> Option<Tree> {
+        let mut matcher = Matcher::new(self, message);
+        matcher.run(id);
+        matcher.tree(id, 0, matcher.message.len(), &mut BTreeSet::new())
+    }
+}
+
+/// Derivation of the characters start..end of a message; Char rules are
+/// the leaves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree {
+    pub rule: usize,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub children: Vec<Tree>,
+}
+
+/// S-expression with the rule ids, where the leaves show their character,
+/// as in `(0 (4 a) (1 ...))`
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}", self.rule)?;
+        if self.children.is_empty() {
+            write!(f, " {}", self.text)?;
+        }
+        for child in &self.children {
+            write!(f, " {}", child)?;
+        }
+        write!(f, ")")
+    }
+}
+
+// Matcher //
+
+/// Memoized top-down matcher that finds, for each rule and starting
+/// position, all the positions where the rule can end.
+///
+/// Left-recursive rules reach themselves at the same position; those
+/// calls use the ends found so far, and the whole search is repeated
+/// until no new ends are found. As every rule consumes at least one
+/// character, that terminates with the exact sets.
+struct Matcher<'a> {
+    grammar: &'a Grammar,
+    message: Vec<char>,
+    memo: HashMap<(usize, usize), BTreeSet<usize>>,
+    /// Entries already computed in the current pass
+    done: HashSet<(usize, usize)>,
+    /// Entries being computed, to detect recursion
+    active: HashSet<(usize, usize)>,
+    recursed: bool,
+    changed: bool,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(grammar: &'a Grammar, message: &str) -> Matcher<'a> {
+        Matcher {
+            grammar,
+            message: message.chars().collect(),
+            memo: HashMap::new(),
+            done: HashSet::new(),
+            active: HashSet::new(),
+            recursed: false,
+            changed: false,
+        }
+    }
+
+    fn run(&mut self, id: usize) {
+        let mut passes = 0;
+        loop {
+            passes += 1;
+            self.done.clear();
+            self.recursed = false;
+            self.changed = false;
+            self.compute(id, 0);
+            if !self.recursed || !self.changed {
+                break;
+            }
+        }
+        trace!("{} passes, {} entries", passes, self.memo.len());
+    }
+
+    fn ends(&self, id: usize, pos: usize) -> BTreeSet<usize> {
+        self.memo.get(&(id, pos)).cloned().unwrap_or_default()
+    }
+
+    fn compute(&mut self, id: usize, pos: usize) -> BTreeSet<usize> {
+        let key = (id, pos);
+        if self.done.contains(&key) {
+            return self.ends(id, pos);
+        }
+        if !self.active.insert(key) {
+            self.recursed = true;
+            return self.ends(id, pos);
+        }
+        let mut ends = BTreeSet::new();
+        match self.grammar.get(id) {
+            Some(Rule::Char(c)) if self.message.get(pos) == Some(c) => {
+                ends.insert(pos + 1);
+            }
+            Some(Rule::Alts(alts)) => {
+                for seq in alts {
+                    let mut positions = BTreeSet::from([pos]);
+                    for &sub in seq {
+                        let mut next = BTreeSet::new();
+                        for p in positions {
+                            next.extend(self.compute(sub, p));
+                        }
+                        positions = next;
+                    }
+                    ends.extend(positions);
+                }
+            }
+            _ => {}
+        }
+        self.active.remove(&key);
+        self.done.insert(key);
+        let entry = self.memo.entry(key).or_default();
+        if *entry != ends {
+            *entry = ends.clone();
+            self.changed = true;
+        }
+        ends
+    }
+
+    /// Build the tree of the rule over start..end from the memo; `path`
+    /// has the trees being built, so that cycles of rules over the same
+    /// span are skipped
+    fn tree(
+        &self,
+        id: usize,
+        start: usize,
+        end: usize,
+        path: &mut BTreeSet<(usize, usize, usize)>,
+    ) -> Option<Tree> {
+        if !self.ends(id, start).contains(&end) || !path.insert((id, start, end)) {
+            return None;
+        }
+        let children = match self.grammar.get(id)? {
+            Rule::Char(_) => Some(vec![]),
+            Rule::Alts(alts) => alts
+                .iter()
+                .find_map(|seq| self.seq_trees(seq, start, end, path)),
+        };
+        path.remove(&(id, start, end));
+        Some(Tree {
+            rule: id,
+            start,
+            end,
+            children: children?,
+            text: self.message[start..end].iter().collect(),
+        })
+    }
+
+    fn seq_trees(
+        &self,
+        seq: &[usize],
+        start: usize,
+        end: usize,
+        path: &mut BTreeSet<(usize, usize, usize)>,
+    ) -> Option<Vec<Tree>> {
+        let (&first, rest) = seq.split_first()?;
+        if rest.is_empty() {
+            return Some(vec![self.tree(first, start, end, path)?]);
+        }
+        for mid in self.ends(first, start) {
+            if mid >= end {
+                continue;
+            }
+            if let Some(trees) = self.seq_trees(rest, mid, end, path) {
+                if let Some(tree) = self.tree(first, start, mid, path) {
+                    let mut all = vec![tree];
+                    all.extend(trees);
+                    return Some(all);
+                }
+            }
+        }
+        None
+    }
+}
+
+pub fn count_matches(grammar: &Grammar, messages: &[String]) -> i64 {
+    let mut count = 0;
+    for message in messages {
+        if grammar.matches(0, message) {
+            trace!("match {}", message);
+            count += 1;
+        }
+    }
+    count
+}
+
+// Solver //
+
+/// The rules and the received messages
+#[derive(Debug, Default, Clone)]
+pub struct Messages {
+    pub grammar: Grammar,
+    pub messages: Vec<String>,
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Input = Messages;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        let mut rules = BTreeMap::new();
+        let re_rule: Regex = Regex::new(r"^(?P<id>[^:]+): (?P<contents>.*)$")?;
+        let mut lines_iter = bufin.lines();
+        let mut lineno = 0;
+        loop {
+            let line = line_next(&mut lines_iter)?;
+            lineno += 1;
+            if line.is_empty() {
+                break;
+            }
+            let m_rule = re_rule
+                .captures(&line)
+                .ok_or_else(|| ParseError::new(19, lineno, 1, &line, "\"<id>: <rule>\""))?;
+            let id = parse_line::<usize>(19, lineno, &m_rule["id"]).map_err(|e| ParseError {
+                text: line.clone(),
+                ..e
+            })?;
+            let contents = m_rule.name("contents").unwrap();
+            let rule = contents.as_str().parse::<Rule>().map_err(|e| ParseError {
+                line: lineno,
+                column: e.column + contents.start(),
+                text: line.clone(),
+                ..e
+            })?;
+            rules.insert(id, rule);
+        }
+        let grammar = Grammar::new(rules)?;
+        let mut messages = vec![];
+        for line_opt in lines_iter {
+            messages.push(line_opt?);
+        }
+        Ok(Messages { grammar, messages })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(count_matches(&input.grammar, &input.messages))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        let mut grammar = input.grammar.clone();
+        grammar.insert(8, "42 | 42 8".parse()?)?;
+        grammar.insert(11, "42 31 | 42 11 31".parse()?)?;
+        debug!("rules 8 and 11 replaced by recursive versions");
+        Ok(count_matches(&grammar, &input.messages))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day19::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day19::run2(bufin)
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    let input: &[u8] = b"0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"\n\nababbb\nbababa\nabbbab\naaabbb\naaaabbb\n";
+    assert_eq!(solve_a(input)?, 2);
+    Ok(())
+}
+
+#[test]
+fn test1_b() -> Result<()> {
+    let input: &[u8] = b"42: 9 14 | 10 1\n9: 14 27 | 1 26\n10: 23 14 | 28 1\n1: \"a\"\n11: 42 31\n5: 1 14 | 15 1\n19: 14 1 | 14 14\n12: 24 14 | 19 1\n16: 15 1 | 14 14\n31: 14 17 | 1 13\n6: 14 14 | 1 14\n2: 1 24 | 14 4\n0: 8 11\n13: 14 3 | 1 12\n15: 1 | 14\n17: 14 2 | 1 7\n23: 25 1 | 22 14\n28: 16 1\n4: 1 1\n20: 14 14 | 1 15\n3: 5 14 | 16 1\n27: 1 6 | 14 18\n14: \"b\"\n21: 14 1 | 1 14\n25: 1 1 | 1 14\n22: 14 14\n8: 42\n26: 14 22 | 1 20\n18: 15 15\n7: 14 5 | 1 21\n24: 14 1\n\nabbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa\nbbabbbbaabaabba\nbabbbbaabbbbbabbbbbbaabaaabaaa\naaabbbbbbaaaabaababaabababbabaaabbababababaaa\nbbbbbbbaaaabbbbaaabbabaaa\nbbbababbbbaaaaaaaabbababaaababaabab\nababaaaaaabaaab\nababaaaaabbbaba\nbaabbaaaabbaaaababbaababb\nabbbbabbbbaaaababbbbbbaaaababb\naaaaabbaabaaaaababaa\naaaabbaaaabbaaa\naaaabbaabbaaaaaaabbbabbbaaabbaabaaa\nbabaaabbbaaabaababbaabababaaab\naabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba\n";
+    assert_eq!(solve_b(input)?, 12);
+    Ok(())
+}
+
+#[test]
+fn test_rule() -> Result<()> {
+    assert_eq!("\"a\"".parse::<Rule>()?, Rule::Char('a'));
+    assert_eq!(
+        "42 31 | 42 11 31".parse::<Rule>()?,
+        Rule::Alts(vec![vec![42, 31], vec![42, 11, 31]])
+    );
+    assert_eq!(
+        "1 x".parse::<Rule>(),
+        Err(ParseError::new(19, 1, 3, "1 x", "a rule id"))
+    );
+    assert_eq!(
+        "1 |".parse::<Rule>(),
+        Err(ParseError::new(19, 1, 4, "1 |", "a rule id"))
+    );
+    let e = Day19::parse(&b"0: 1\n1: 2 |\n\n"[..]).unwrap_err();
+    assert_eq!(
+        e.downcast::<ParseError>()?,
+        ParseError::new(19, 2, 7, "1: 2 |", "a rule id")
+    );
+    let e = Day19::parse(&b"0: 1 2\n1: \"a\"\n\n"[..]).unwrap_err();
+    assert_eq!(e.to_string(), "rule 0 refers to missing rule 2");
+    Ok(())
+}
+
+#[test]
+fn test_recursion() -> Result<()> {
+    // Deeper than the old regex unrolling could go
+    let input: &[u8] = b"0: 8 11\n8: 42 | 42 8\n11: 42 31 | 42 11 31\n42: \"a\"\n31: \"b\"\n\n";
+    let grammar = Day19::parse(input)?.grammar;
+    let message = |a: usize, b: usize| format!("{}{}", "a".repeat(a), "b".repeat(b));
+    assert!(grammar.matches(0, &message(13, 12)));
+    assert!(grammar.matches(0, &message(40, 30)));
+    assert!(!grammar.matches(0, &message(12, 12)));
+    assert!(!grammar.matches(0, &message(30, 31)));
+    // Left recursion and a cycle of single-rule alternatives
+    let input: &[u8] = b"0: 0 1 | 1\n1: 2 | 3\n2: 1 | 4\n3: \"a\"\n4: \"b\"\n\n";
+    let grammar = Day19::parse(input)?.grammar;
+    assert!(grammar.matches(0, "abba"));
+    assert!(!grammar.matches(0, "abca"));
+    assert!(!grammar.matches(0, ""));
+    let tree = grammar.parse(0, "ab").unwrap();
+    assert_eq!(tree.to_string(), "(0 (0 (1 (3 a))) (1 (2 (4 b))))");
+    Ok(())
+}
+
+#[test]
+fn test_tree() -> Result<()> {
+    let input: &[u8] =
+        b"0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"\n\n";
+    let grammar = Day19::parse(input)?.grammar;
+    let tree = grammar.parse(0, "ababbb").unwrap();
+    assert_eq!(
+        tree.to_string(),
+        "(0 (4 a) (1 (3 (5 b) (4 a)) (2 (5 b) (5 b))) (5 b))"
+    );
+    assert_eq!((tree.children[1].start, tree.children[1].end), (1, 5));
+    assert_eq!(grammar.parse(0, "bababa"), None);
+    Ok(())
+}
@@ -0,0 +1,669 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::grid::{Grid, Orientation, View, Xy};
+use crate::pattern::{Match, Pattern};
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{ensure, Result};
+use lazy_static::lazy_static;
+use log::{debug, info};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::BufRead;
+
+// Tile //
+
+/// Edges are read clockwise, so two tiles fit when the touching edge of
+/// one is the reverse of the other
+pub type Edge = Vec<bool>;
+
+fn reversed(edge: Edge) -> Edge {
+    edge.into_iter().rev().collect()
+}
+
+/// Square tile of any size, with `true` for '#'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tile {
+    pub id: i64,
+    pub image: Grid<bool>,
+}
+
+impl Tile {
+    pub fn new(id: i64, image: Grid<bool>) -> Result<Tile> {
+        ensure!(
+            image.width() == image.height() && image.width() >= 2,
+            "tile {} is {}x{}, expected a square of side 2 or more",
+            id,
+            image.width(),
+            image.height()
+        );
+        Ok(Tile { id, image })
+    }
+
+    pub fn size(&self) -> usize {
+        self.image.width()
+    }
+
+    pub fn view(&self, orientation: Orientation) -> View<'_, bool> {
+        self.image.view(orientation)
+    }
+
+    /// The top, right, bottom and left edges in the given orientation
+    pub fn edges(&self, orientation: Orientation) -> [Edge; 4] {
+        let view = self.view(orientation);
+        let last = self.size() as i32 - 1;
+        let edge = |xys: &mut dyn Iterator<Item = (i32, i32)>| {
+            xys.map(|(x, y)| view[Xy::new(x, y)]).collect::<Edge>()
+        };
+        [
+            edge(&mut (0..=last).map(|x| (x, 0))),
+            edge(&mut (0..=last).map(|y| (last, y))),
+            edge(&mut (0..=last).rev().map(|x| (x, last))),
+            edge(&mut (0..=last).rev().map(|y| (0, y))),
+        ]
+    }
+
+    pub fn hash_count(&self) -> usize {
+        self.image.iter().filter(|(_, &c)| c).count()
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Tile {}:", self.id)?;
+        write!(f, "{}", self.image.render(|&c| if c { '#' } else { '.' }))
+    }
+}
+
+// Parser: //
+pub mod parser {
+    use super::Tile;
+    use crate::grid::Grid;
+    use nom::{
+        branch::alt, bytes::complete::tag, character::complete::char, character::complete::digit1,
+        combinator::all_consuming, combinator::map_res, combinator::value, combinator::verify,
+        multi::count, multi::many0, multi::many1, multi::separated_list1, IResult,
+    };
+    use std::str::FromStr;
+
+    pub fn cell(input: &str) -> IResult<&str, bool> {
+        alt((value(false, char('.')), value(true, char('#'))))(input)
+    }
+
+    /// The rows of a tile, which has as many rows as the first one has
+    /// cells
+    pub fn rows(input: &str) -> IResult<&str, Vec<Vec<bool>>> {
+        let (input, first) = verify(many1(cell), |r: &Vec<bool>| r.len() >= 2)(input)?;
+        let (input, _) = char('\n')(input)?;
+        let size = first.len();
+        let (input, rest) = count(
+            |i| {
+                let (i, row) = count(cell, size)(i)?;
+                let (i, _) = char('\n')(i)?;
+                Ok((i, row))
+            },
+            size - 1,
+        )(input)?;
+        let mut rows = vec![first];
+        rows.extend(rest);
+        Ok((input, rows))
+    }
+
+    pub fn tile(input: &str) -> IResult<&str, Tile> {
+        let (input, _) = tag("Tile ")(input)?;
+        let (input, id) = map_res(digit1, i64::from_str)(input)?;
+        let (input, _) = tag(":\n")(input)?;
+        let (input, image) = map_res(rows, Grid::from_rows)(input)?;
+        Ok((input, Tile { id, image }))
+    }
+
+    pub fn alltiles(input: &str) -> IResult<&str, Vec<Tile>> {
+        let (input, tiles) = separated_list1(char('\n'), tile)(input)?;
+        let (input, _) = many0(char('\n'))(input)?;
+        Ok((input, tiles))
+    }
+
+    pub fn allinput(input: &str) -> IResult<&str, Vec<Tile>> {
+        all_consuming(alltiles)(input)
+    }
+}
+
+// Assembly //
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlacementError {
+    /// The tile doesn't have the size of the first one
+    Size {
+        tile: i64,
+        size: usize,
+        expected: usize,
+    },
+    /// The number of tiles doesn't fill the rectangle
+    Count {
+        tiles: usize,
+        width: usize,
+        height: usize,
+    },
+    /// None of the edges of the tile fit an edge of another tile
+    Unmatched { tile: i64 },
+    /// The search got stuck at `pos`, where none of the unplaced tiles
+    /// fits the neighbors to the left and on top
+    NoFit {
+        pos: (usize, usize),
+        left: Option<i64>,
+        top: Option<i64>,
+        unplaced: Vec<i64>,
+    },
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::Size {
+                tile,
+                size,
+                expected,
+            } => write!(
+                f,
+                "tile {} has side {}, expected {} like the others",
+                tile, size, expected
+            ),
+            PlacementError::Count {
+                tiles,
+                width,
+                height,
+            } => write!(f, "{} tiles can't fill {}x{}", tiles, width, height),
+            PlacementError::Unmatched { tile } => {
+                write!(f, "tile {} has no edge in common with other tiles", tile)
+            }
+            PlacementError::NoFit {
+                pos,
+                left,
+                top,
+                unplaced,
+            } => {
+                let ids = unplaced.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+                if let [id] = &ids[..] {
+                    write!(f, "tile {} doesn't fit at {:?}", id, pos)?;
+                } else {
+                    write!(f, "none of the tiles {} fits at {:?}", ids.join(", "), pos)?;
+                }
+                match (left, top) {
+                    (Some(l), Some(t)) => write!(f, ", right of tile {} and below tile {}", l, t),
+                    (Some(l), None) => write!(f, ", right of tile {}", l),
+                    (None, Some(t)) => write!(f, ", below tile {}", t),
+                    (None, None) => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
+/// A tile and the orientation it was placed with
+#[derive(Debug, Clone)]
+pub struct Placed {
+    pub tile: Tile,
+    pub orientation: Orientation,
+}
+
+impl Placed {
+    pub fn view(&self) -> View<'_, bool> {
+        self.tile.view(self.orientation)
+    }
+}
+
+/// The placed tiles, in row-major order
+#[derive(Debug, Clone)]
+pub struct Assembly {
+    pub width: usize,
+    pub height: usize,
+    pub tiles: Vec<Placed>,
+}
+
+impl Assembly {
+    pub fn at(&self, x: usize, y: usize) -> &Placed {
+        &self.tiles[y * self.width + x]
+    }
+
+    /// Top-left, top-right, bottom-left and bottom-right
+    pub fn corners(&self) -> [&Placed; 4] {
+        let (w, h) = (self.width - 1, self.height - 1);
+        [self.at(0, 0), self.at(w, 0), self.at(0, h), self.at(w, h)]
+    }
+
+    /// Merge the tiles into a single image, dropping their borders
+    pub fn merge(&self) -> Grid<char> {
+        let side = self.tiles[0].tile.size() - 2;
+        let mut image = Grid::new(self.width * side, self.height * side, '.');
+        for (i, placed) in self.tiles.iter().enumerate() {
+            let origin = Xy::new(
+                ((i % self.width) * side) as i32,
+                ((i / self.width) * side) as i32,
+            );
+            for (xy, &c) in placed.view().iter() {
+                let inner = xy - Xy::new(1, 1);
+                if (0..side as i32).contains(&inner.x) && (0..side as i32).contains(&inner.y) && c {
+                    image[origin + inner] = '#';
+                }
+            }
+        }
+        image
+    }
+}
+
+/// Backtracking search that fills the rectangle row by row
+struct Placer<'a> {
+    tiles: &'a [Tile],
+    width: usize,
+    height: usize,
+    /// Every tile index in every orientation, with its edges
+    oriented: Vec<(usize, Orientation, [Edge; 4])>,
+    by_left: BTreeMap<Edge, Vec<usize>>,
+    by_top: BTreeMap<Edge, Vec<usize>>,
+    placed: Vec<usize>,
+    used: BTreeSet<i64>,
+    /// The failure after the most tiles placed
+    deepest: Option<(usize, PlacementError)>,
+}
+
+impl<'a> Placer<'a> {
+    fn new(tiles: &'a [Tile], width: usize, height: usize) -> Placer<'a> {
+        let mut oriented = vec![];
+        for (i, tile) in tiles.iter().enumerate() {
+            for o in Orientation::ALL {
+                oriented.push((i, o, tile.edges(o)));
+            }
+        }
+        let mut by_left = BTreeMap::<Edge, Vec<usize>>::new();
+        let mut by_top = BTreeMap::<Edge, Vec<usize>>::new();
+        for (i, (_, _, [top, _, _, left])) in oriented.iter().enumerate() {
+            by_left.entry(left.clone()).or_default().push(i);
+            by_top.entry(top.clone()).or_default().push(i);
+        }
+        Placer {
+            tiles,
+            width,
+            height,
+            oriented,
+            by_left,
+            by_top,
+            placed: vec![],
+            used: BTreeSet::new(),
+            deepest: None,
+        }
+    }
+
+    fn id(&self, oriented: usize) -> i64 {
+        self.tiles[self.oriented[oriented].0].id
+    }
+
+    /// Candidates for the top-left position, starting with the ones that
+    /// have the fewest matches on their top and left edges, which are the
+    /// likely corners
+    fn first_candidates(&self) -> Vec<usize> {
+        let matches = |edge: &Edge, index: &BTreeMap<Edge, Vec<usize>>| {
+            index.get(&reversed(edge.clone())).map_or(0, |v| v.len())
+        };
+        let mut candidates = (0..self.oriented.len()).collect::<Vec<_>>();
+        candidates.sort_by_key(|&i| {
+            let [top, _, _, left] = &self.oriented[i].2;
+            matches(top, &self.by_top) + matches(left, &self.by_left)
+        });
+        candidates
+    }
+
+    fn place(&mut self) -> bool {
+        let pos = self.placed.len();
+        if pos == self.width * self.height {
+            return true;
+        }
+        let (x, y) = (pos % self.width, pos / self.width);
+        let left = (x > 0).then(|| self.placed[pos - 1]);
+        let top = (y > 0).then(|| self.placed[pos - self.width]);
+        let want_top = top.map(|t| reversed(self.oriented[t].2[2].clone()));
+        let candidates = match (left, &want_top) {
+            (Some(l), _) => self
+                .by_left
+                .get(&reversed(self.oriented[l].2[1].clone()))
+                .cloned(),
+            (None, Some(t)) => self.by_top.get(t).cloned(),
+            (None, None) => Some(self.first_candidates()),
+        };
+        for c in candidates.unwrap_or_default() {
+            let id = self.id(c);
+            if self.used.contains(&id) {
+                continue;
+            }
+            if let Some(t) = &want_top {
+                if self.oriented[c].2[0] != *t {
+                    continue;
+                }
+            }
+            self.used.insert(id);
+            self.placed.push(c);
+            if self.place() {
+                return true;
+            }
+            self.placed.pop();
+            self.used.remove(&id);
+        }
+        if self.deepest.as_ref().is_none_or(|(d, _)| pos >= *d) {
+            let unplaced = self
+                .tiles
+                .iter()
+                .map(|t| t.id)
+                .filter(|id| !self.used.contains(id))
+                .collect();
+            let err = PlacementError::NoFit {
+                pos: (x, y),
+                left: left.map(|l| self.id(l)),
+                top: top.map(|t| self.id(t)),
+                unplaced,
+            };
+            self.deepest = Some((pos, err));
+        }
+        false
+    }
+}
+
+/// Checks that don't depend on the shape of the rectangle
+fn check_tiles(tiles: &[Tile]) -> Result<(), PlacementError> {
+    let expected = tiles.first().map_or(0, |t| t.size());
+    if let Some(t) = tiles.iter().find(|t| t.size() != expected) {
+        return Err(PlacementError::Size {
+            tile: t.id,
+            size: t.size(),
+            expected,
+        });
+    }
+    if tiles.len() > 1 {
+        // Count the tiles that have each edge, in both directions
+        let mut owners = BTreeMap::<Edge, BTreeSet<i64>>::new();
+        for t in tiles {
+            for edge in t.edges(Orientation::Identity) {
+                owners.entry(edge.clone()).or_default().insert(t.id);
+                owners.entry(reversed(edge)).or_default().insert(t.id);
+            }
+        }
+        for t in tiles {
+            if t.edges(Orientation::Identity)
+                .iter()
+                .all(|e| owners[e].len() == 1)
+            {
+                return Err(PlacementError::Unmatched { tile: t.id });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Place the tiles in a rectangle with the given number of tiles per row
+/// and column
+pub fn assemble_rect(
+    tiles: &[Tile],
+    width: usize,
+    height: usize,
+) -> Result<Assembly, PlacementError> {
+    if tiles.is_empty() || tiles.len() != width * height {
+        return Err(PlacementError::Count {
+            tiles: tiles.len(),
+            width,
+            height,
+        });
+    }
+    check_tiles(tiles)?;
+    let mut placer = Placer::new(tiles, width, height);
+    if !placer.place() {
+        return Err(placer.deepest.unwrap().1);
+    }
+    let placed = placer.placed.iter().map(|&i| {
+        let (t, orientation, _) = placer.oriented[i];
+        Placed {
+            tile: tiles[t].clone(),
+            orientation,
+        }
+    });
+    Ok(Assembly {
+        width,
+        height,
+        tiles: placed.collect(),
+    })
+}
+
+/// Place the tiles in the squarest rectangle where they fit; as any
+/// arrangement can be rotated, the rectangles tried are never taller than
+/// wide
+pub fn assemble(tiles: &[Tile]) -> Result<Assembly, PlacementError> {
+    let n = tiles.len();
+    let mut shapes = (1..=n)
+        .filter(|&w| n.is_multiple_of(w) && w >= n / w)
+        .map(|w| (w, n / w))
+        .collect::<Vec<_>>();
+    shapes.sort_by_key(|&(w, h)| w - h);
+    let mut error = PlacementError::Count {
+        tiles: n,
+        width: 0,
+        height: 0,
+    };
+    for (width, height) in shapes {
+        debug!("trying {}x{}", width, height);
+        match assemble_rect(tiles, width, height) {
+            Ok(assembly) => return Ok(assembly),
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+// Monsters //
+
+lazy_static! {
+    pub static ref MONSTER: Pattern =
+        "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   \n"
+            .parse()
+            .unwrap();
+}
+
+/// Where the sea monsters are, in any orientation
+pub fn find_monsters(image: &Grid<char>) -> Vec<Match> {
+    MONSTER.find(image, |&c| c == '#')
+}
+
+pub fn monster_count(image: &Grid<char>) -> usize {
+    find_monsters(image).len()
+}
+
+// Solver //
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Vec<Tile>;
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    fn parse(mut bufin: impl BufRead) -> Result<Self::Input> {
+        let mut input = String::default();
+        bufin.read_to_string(&mut input)?;
+        Ok(parser::allinput(&input)
+            .map_err(|e| ParseError::from_nom(20, &input, e))?
+            .1)
+    }
+
+    fn part1(tiles: &Self::Input) -> Result<Self::Answer1> {
+        let assembly = assemble(tiles)?;
+        Ok(assembly.corners().iter().map(|p| p.tile.id).product())
+    }
+
+    fn part2(tiles: &Self::Input) -> Result<Self::Answer2> {
+        let image = assemble(tiles)?.merge();
+        let monsters = find_monsters(&image);
+        info!("monsters found: {}", monsters.len());
+        for m in &monsters {
+            debug!("monster at {:?}, {}", m.xy, m.orientation);
+        }
+        // Monsters can overlap, so count what's left after marking them
+        let marked = MONSTER.highlight(&image, &monsters, 'O');
+        debug!("image with monsters:\n{}", marked);
+        Ok(marked.iter().filter(|(_, &c)| c == '#').count())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day20::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day20::run2(bufin)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Tile 2311:\n..##.#..#.\n##..#.....\n#...##..#.\n####.#...#\n##.##.###.\n##...#.###\n.#.#.#..##\n..#....#..\n###...#.#.\n..###..###\n\nTile 1951:\n#.##...##.\n#.####...#\n.....#..##\n#...######\n.##.#....#\n.###.#####\n###.##.##.\n.###....#.\n..#.#..#.#\n#...##.#..\n\nTile 1171:\n####...##.\n#..##.#..#\n##.#..#.#.\n.###.####.\n..###.####\n.##....##.\n.#...####.\n#.##.####.\n####..#...\n.....##...\n\nTile 1427:\n###.##.#..\n.#..#.##..\n.#.##.#..#\n#.#.#.##.#\n....#...##\n...##..##.\n...#.#####\n.#.####.#.\n..#..###.#\n..##.#..#.\n\nTile 1489:\n##.#.#....\n..##...#..\n.##..##...\n..#...#...\n#####...#.\n#..#.#.#.#\n...#.#.#..\n##.#...##.\n..##.##.##\n###.##.#..\n\nTile 2473:\n#....####.\n#..#.##...\n#.##..#...\n######.#.#\n.#...#.#.#\n.#########\n.###.#..#.\n########.#\n##...##.#.\n..###.#.#.\n\nTile 2971:\n..#.#....#\n#...###...\n#.#.###...\n##.##..#..\n.#####..##\n.#..####.#\n#..#.#..#.\n..####.###\n..#.#.###.\n...#.#.#.#\n\nTile 2729:\n...#.#.#.#\n####.#....\n..#.#.....\n....#..#.#\n.##..##.#.\n.#.####...\n####.#.#..\n##.####...\n##..#.##..\n#.##...##.\n\nTile 3079:\n#.#.#####.\n.#..######\n..#.......\n######....\n####.#..#.\n.#...#.##.\n#.#####.##\n..#.###...\n..#.......\n..#.###...\n";
+
+#[test]
+fn test_parser_tile_a() -> Result<()> {
+    let t = parser::tile("Tile 1:\n........#.\n#.........\n..........\n..........\n..........\n..........\n..........\n..........\n.........#\n.#........\n")?;
+    let two = (0..10).map(|i| i == 8).collect::<Edge>();
+    assert_eq!(t.1.id, 1);
+    for edge in t.1.edges(Orientation::Identity) {
+        assert_eq!(edge, two);
+    }
+    Ok(())
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    assert_eq!(solve_a(EXAMPLE.as_bytes())?, 20899048083289);
+    Ok(())
+}
+
+#[test]
+fn test2_a() -> Result<()> {
+    let tiles = parser::allinput(EXAMPLE)
+        .map_err(|e| ParseError::from_nom(20, EXAMPLE, e))?
+        .1;
+    let bits = |n: u16| (0..10).rev().map(|i| n & (1 << i) != 0).collect::<Edge>();
+    let tile2311 = tiles[0].clone();
+    assert_eq!(tile2311.id, 2311);
+    let tile1951 = tiles[1].clone();
+    assert_eq!(tile1951.id, 1951);
+    let tile1171 = tiles[2].clone();
+    assert_eq!(tile1171.id, 1171);
+    let tile1427 = tiles[3].clone();
+    assert_eq!(tile1427.id, 1427);
+    let tile1489 = tiles[4].clone();
+    assert_eq!(tile1489.id, 1489);
+    let tile2473 = tiles[5].clone();
+    assert_eq!(tile2473.id, 2473);
+    let tile2971 = tiles[6].clone();
+    assert_eq!(tile2971.id, 2971);
+    let tile2729 = tiles[7].clone();
+    assert_eq!(tile2729.id, 2729);
+    let tile3079 = tiles[8].clone();
+    assert_eq!(tile3079.id, 3079);
+    assert_eq!(tile1951.edges(Orientation::FlipV)[0], bits(0x234));
+    assert_eq!(tile2311.edges(Orientation::FlipV)[0], bits(0x0e7));
+    assert_eq!(tile3079.edges(Orientation::Identity)[0], bits(0x2be));
+    assert_eq!(tile2729.edges(Orientation::FlipV)[0], bits(0x2c6));
+    assert_eq!(tile1427.edges(Orientation::FlipV)[0], bits(0x0d2));
+    let o = Orientation::Rotate90.then(Orientation::FlipV);
+    assert_eq!(o, Orientation::AntiTranspose);
+    assert_eq!(tile2473.edges(o)[0], bits(0x0b8));
+    assert_eq!(tile2971.edges(Orientation::FlipV)[0], bits(0x055));
+    assert_eq!(tile1489.edges(Orientation::FlipV)[0], bits(0x3b4));
+    assert_eq!(tile1171.edges(Orientation::FlipH)[0], bits(0x18f));
+    let tiles = vec![tile1951, tile2311, tile2729, tile1427];
+    let assembly = assemble_rect(&tiles, 2, 2)?;
+    assert_eq!(assembly.tiles.len(), 4);
+    // Starting from a corner, the other tiles keep the orientation of the
+    // example
+    let corner = assembly.at(0, 0).orientation;
+    for placed in &assembly.tiles {
+        assert_eq!(placed.orientation, corner);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_monstercount_b() -> Result<()> {
+    let str0=".####...#####..#...###..\n#####..#..#.#.####..#.#.\n.#.#...#.###...#.##.##..\n#.#.##.###.#.##.##.#####\n..##.###.####..#.####.##\n...#.#..##.##...#..#..##\n#.##.#..#.#..#..##.#.#..\n.###.##.....#...###.#...\n#.####.#.#....##.#..#.#.\n##...#..#....#..#...####\n..#.##...###..#.#####..#\n....#.##.#.#####....#...\n..##.##.###.....#.##..#.\n#...#...###..####....##.\n.#.##...#.##.#.#.###...#\n#.###.#..####...##..#...\n#.###...#.##...#.######.\n.###.###.#######..#####.\n..##.#..#..#.#######.###\n#.#..##.########..#..##.\n#.#####..#.#...##..#....\n#....##..#.#########..##\n#...#.....#..##...###.##\n#..###....##.#...##.##.#\n";
+    let image = str0.parse::<Grid<char>>()?;
+    assert_eq!(monster_count(&image), 2);
+    let monsters = find_monsters(&image);
+    assert_eq!(monsters[0].orientation, monsters[1].orientation);
+    let marked = MONSTER.highlight(&image, &monsters, 'O');
+    assert_eq!(marked.iter().filter(|(_, &c)| c == 'O').count(), 30);
+    assert_eq!(marked.iter().filter(|(_, &c)| c == '#').count(), 273);
+    let image = image.flip_h();
+    assert_eq!(monster_count(&image), 2);
+    let image = image.flip_v();
+    assert_eq!(monster_count(&image), 2);
+    Ok(())
+}
+
+#[test]
+fn test1_b() -> Result<()> {
+    assert_eq!(solve_b(EXAMPLE.as_bytes())?, 273);
+    Ok(())
+}
+
+#[test]
+fn test_rect() -> Result<()> {
+    let tiles = Day20::parse(EXAMPLE.as_bytes())?;
+    let pick = |ids: &[i64]| {
+        tiles
+            .iter()
+            .filter(|t| ids.contains(&t.id))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    // The two top rows of the example
+    let assembly = assemble(&pick(&[1951, 2311, 3079, 2729, 1427, 2473]))?;
+    assert_eq!((assembly.width, assembly.height), (3, 2));
+    let mut corners = assembly.corners().map(|p| p.tile.id);
+    corners.sort_unstable();
+    assert_eq!(corners, [1951, 2473, 2729, 3079]);
+    assert_eq!(assembly.merge().width(), 24);
+    // The top and bottom rows don't fit together
+    let err = assemble(&pick(&[1951, 2311, 3079, 2971, 1489, 1171])).unwrap_err();
+    assert!(matches!(err, PlacementError::NoFit { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_placement_errors() -> Result<()> {
+    let mut tiles = Day20::parse(EXAMPLE.as_bytes())?;
+    assert_eq!(
+        assemble_rect(&tiles, 2, 2).unwrap_err().to_string(),
+        "9 tiles can't fill 2x2"
+    );
+    let mut with_small = tiles.clone();
+    with_small[4] = Tile::new(
+        7,
+        "#.#\n...\n#.#\n".parse::<Grid<char>>()?.map(|&c| c == '#'),
+    )?;
+    assert_eq!(
+        assemble(&with_small).unwrap_err(),
+        PlacementError::Size {
+            tile: 7,
+            size: 3,
+            expected: 10
+        }
+    );
+    tiles[4] = Tile::new(7, Grid::new(10, 10, true))?;
+    assert_eq!(
+        assemble_rect(&tiles, 3, 3).unwrap_err().to_string(),
+        "tile 7 has no edge in common with other tiles"
+    );
+    let err = parser::allinput("Tile 1:\n#.#\n...\n##\n").unwrap_err();
+    assert_eq!(
+        ParseError::from_nom(20, "Tile 1:\n#.#\n...\n##\n", err).line,
+        4
+    );
+    Ok(())
+}
@@ -0,0 +1,355 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::matching::{assignments, matching};
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use log::{debug, trace};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::BufRead;
+
+// Ingredient, Allergen //
+
+pub type Ingredient = String;
+
+pub type Allergen = String;
+
+// Food //
+
+#[derive(Debug)]
+pub struct Food {
+    pub ingredients: Vec<Ingredient>,
+    pub allergens: Vec<Allergen>,
+}
+
+impl Food {
+    pub fn new(ingredients: Vec<Ingredient>, allergens: Vec<Allergen>) -> Food {
+        Food {
+            ingredients,
+            allergens,
+        }
+    }
+}
+
+// Parser: //
+
+pub mod parser {
+    use super::{Allergen, Food, Ingredient};
+    use nom::{
+        bytes::complete::tag, character::complete::alpha1, character::complete::char,
+        combinator::all_consuming, combinator::map, multi::many0, multi::separated_list1, IResult,
+    };
+
+    pub fn ingredient(input: &str) -> IResult<&str, Ingredient> {
+        map(alpha1, |s: &str| s.parse::<Ingredient>().unwrap())(input)
+    }
+
+    pub fn allergen(input: &str) -> IResult<&str, Allergen> {
+        map(alpha1, |s: &str| s.parse::<Allergen>().unwrap())(input)
+    }
+
+    pub fn line(input: &str) -> IResult<&str, Food> {
+        let (input, ingredients) = separated_list1(tag(" "), ingredient)(input)?;
+        let (input, _) = tag(" (contains ")(input)?;
+        let (input, allergens) = separated_list1(tag(", "), allergen)(input)?;
+        let (input, _) = tag(")")(input)?;
+        Ok((input, Food::new(ingredients, allergens)))
+    }
+
+    pub fn alllines(input: &str) -> IResult<&str, Vec<Food>> {
+        let (input, lines) = separated_list1(char('\n'), line)(input)?;
+        let (input, _) = many0(char('\n'))(input)?;
+        Ok((input, lines))
+    }
+
+    pub fn allinput(input: &str) -> IResult<&str, Vec<Food>> {
+        all_consuming(alllines)(input)
+    }
+}
+
+pub fn get_ingredients(foods: &[Food]) -> BTreeSet<Ingredient> {
+    foods
+        .iter()
+        .flat_map(|f| f.ingredients.iter())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+}
+
+pub fn get_allergens(foods: &[Food]) -> BTreeSet<Allergen> {
+    foods
+        .iter()
+        .flat_map(|f| f.allergens.iter())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+}
+
+// Deduction //
+
+/// Which ingredient has each allergen
+pub type Assignment = BTreeMap<Allergen, Ingredient>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Deduction {
+    /// Allergens that can't all be in different ingredients
+    NoSolution(Vec<Allergen>),
+    Unique(Assignment),
+    /// All the consistent assignments, up to MAX_SOLUTIONS of them
+    Multiple(Vec<Assignment>),
+}
+
+pub const MAX_SOLUTIONS: usize = 1000;
+
+/// For each allergen, the ingredients that can contain it: the ones that
+/// are in all foods that list the allergen
+pub fn candidates(foods: &[Food]) -> BTreeMap<Allergen, BTreeSet<Ingredient>> {
+    let mut cands = BTreeMap::<Allergen, BTreeSet<Ingredient>>::new();
+    for food in foods {
+        let ingredients = food.ingredients.iter().cloned().collect::<BTreeSet<_>>();
+        for allergen in &food.allergens {
+            cands
+                .entry(allergen.clone())
+                .and_modify(|c| c.retain(|i| ingredients.contains(i)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    cands
+}
+
+/// Remove the ingredient of each allergen that has a single candidate
+/// from the candidates of the other allergens, until nothing changes
+pub fn propagate(cands: &mut BTreeMap<Allergen, BTreeSet<Ingredient>>) {
+    let mut done = BTreeSet::<Allergen>::new();
+    while let Some((allergen, ingredient)) = cands
+        .iter()
+        .find(|(a, c)| c.len() == 1 && !done.contains(*a))
+        .map(|(a, c)| (a.clone(), c.iter().next().unwrap().clone()))
+    {
+        trace!("{} is in {}", allergen, ingredient);
+        for (other, c) in cands.iter_mut() {
+            if *other != allergen {
+                c.remove(&ingredient);
+            }
+        }
+        done.insert(allergen);
+    }
+}
+
+/// Find the ingredient of each allergen, where each ingredient has at most
+/// one allergen
+pub fn deduce(foods: &[Food]) -> Deduction {
+    deduce_from(&candidates(foods))
+}
+
+fn deduce_from(cands: &BTreeMap<Allergen, BTreeSet<Ingredient>>) -> Deduction {
+    let allergens = cands.keys().collect::<Vec<_>>();
+    let ingredients = cands.values().flatten().collect::<BTreeSet<_>>();
+    let ingredients = ingredients.into_iter().collect::<Vec<_>>();
+    // Candidates as indexes of ingredients
+    let indexes = |cands: &BTreeMap<Allergen, BTreeSet<Ingredient>>| {
+        cands
+            .values()
+            .map(|c| {
+                c.iter()
+                    .filter_map(|i| ingredients.binary_search(&i).ok())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    if let Err(items) = matching(&indexes(cands), ingredients.len()) {
+        return Deduction::NoSolution(items.into_iter().map(|a| allergens[a].clone()).collect());
+    }
+    // Propagation doesn't change the solutions, but leaves less to
+    // enumerate; the conflict above is clearer without it
+    let mut propagated = cands.clone();
+    propagate(&mut propagated);
+    let mut all = assignments(&indexes(&propagated), MAX_SOLUTIONS)
+        .into_iter()
+        .map(|assignment| {
+            assignment
+                .into_iter()
+                .enumerate()
+                .map(|(a, i)| (allergens[a].clone(), ingredients[i].clone()))
+                .collect::<Assignment>()
+        })
+        .collect::<Vec<_>>();
+    debug!("found {} assignment(s)", all.len());
+    if all.len() == 1 {
+        Deduction::Unique(all.remove(0))
+    } else {
+        Deduction::Multiple(all)
+    }
+}
+
+// Report //
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Ingredients that can't contain any allergen, with the number of
+    /// times they appear in the foods
+    pub safe: BTreeMap<Ingredient, usize>,
+    pub deduction: Deduction,
+}
+
+impl Report {
+    pub fn new(foods: &[Food]) -> Report {
+        let cands = candidates(foods);
+        let unsafe_ingredients = cands.values().flatten().collect::<BTreeSet<_>>();
+        let mut safe = BTreeMap::<Ingredient, usize>::new();
+        for ingredient in foods.iter().flat_map(|f| f.ingredients.iter()) {
+            if !unsafe_ingredients.contains(ingredient) {
+                *safe.entry(ingredient.clone()).or_default() += 1;
+            }
+        }
+        Report {
+            safe,
+            deduction: deduce_from(&cands),
+        }
+    }
+
+    /// Number of times safe ingredients appear in the foods
+    pub fn safe_occurrences(&self) -> usize {
+        self.safe.values().sum()
+    }
+
+    /// The dangerous ingredients, sorted by their allergen
+    pub fn dangerous(&self) -> Result<Vec<&Ingredient>> {
+        match &self.deduction {
+            Deduction::Unique(assignment) => Ok(assignment.values().collect()),
+            Deduction::Multiple(all) => Err(anyhow!(
+                "ambiguous allergens, {}{} possible assignments",
+                if all.len() == MAX_SOLUTIONS {
+                    "at least "
+                } else {
+                    ""
+                },
+                all.len()
+            )),
+            Deduction::NoSolution(allergens) => Err(anyhow!(
+                "allergens {} can't all be in different ingredients",
+                allergens.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "safe ingredients: {} ({} occurrences)",
+            self.safe.len(),
+            self.safe_occurrences()
+        )?;
+        for (ingredient, count) in &self.safe {
+            writeln!(f, "  {}: {}", ingredient, count)?;
+        }
+        match &self.deduction {
+            Deduction::Unique(assignment) => {
+                for (allergen, ingredient) in assignment {
+                    writeln!(f, "{}: {}", allergen, ingredient)?;
+                }
+                Ok(())
+            }
+            _ => match self.dangerous() {
+                Err(e) => writeln!(f, "{}", e),
+                Ok(_) => Ok(()),
+            },
+        }
+    }
+}
+
+// Solver //
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input = Vec<Food>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(mut bufin: impl BufRead) -> Result<Self::Input> {
+        let mut input = String::default();
+        bufin.read_to_string(&mut input)?;
+        Ok(parser::allinput(&input)
+            .map_err(|e| ParseError::from_nom(21, &input, e))?
+            .1)
+    }
+
+    fn part1(foods: &Self::Input) -> Result<Self::Answer1> {
+        Ok(Report::new(foods).safe_occurrences())
+    }
+
+    fn part2(foods: &Self::Input) -> Result<Self::Answer2> {
+        let report = Report::new(foods);
+        Ok(itertools::join(report.dangerous()?, ","))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day21::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<String> {
+    Day21::run2(bufin)
+}
+
+// The example, where the free ingredients are kfcds, nhms, sbzzf and trh,
+// and dairy is in mxmxvkd, fish in sqjhc and soy in fvjkl
+#[cfg(test)]
+const EXAMPLE: &[u8] = b"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\ntrh fvjkl sbzzf mxmxvkd (contains dairy)\nsqjhc fvjkl (contains soy)\nsqjhc mxmxvkd sbzzf (contains fish)\n";
+
+#[test]
+fn test1_a() -> Result<()> {
+    assert_eq!(solve_a(EXAMPLE)?, 5);
+    Ok(())
+}
+
+#[test]
+fn test1_b() -> Result<()> {
+    assert_eq!(solve_b(EXAMPLE)?, "mxmxvkd,sqjhc,fvjkl");
+    Ok(())
+}
+
+#[test]
+fn test_report() -> Result<()> {
+    let report = Report::new(&Day21::parse(EXAMPLE)?);
+    assert_eq!(
+        report.to_string(),
+        "safe ingredients: 4 (5 occurrences)\n  kfcds: 1\n  nhms: 1\n  sbzzf: 2\n  trh: 1\n\
+         dairy: mxmxvkd\nfish: sqjhc\nsoy: fvjkl\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deduction() -> Result<()> {
+    // x and y can be in either a or b
+    let foods = Day21::parse(&b"a b c (contains x)\nb a (contains x, y)\n"[..])?;
+    let report = Report::new(&foods);
+    assert_eq!(report.safe, BTreeMap::from([("c".to_string(), 1)]));
+    match &report.deduction {
+        Deduction::Multiple(all) => assert_eq!(all.len(), 2),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(report.dangerous().is_err());
+    // x and y are both only in a; b and c are safe
+    let foods = Day21::parse(
+        &b"a b (contains x)\na c (contains y, x)\na (contains y)\nd (contains z)\n"[..],
+    )?;
+    let report = Report::new(&foods);
+    assert_eq!(report.safe_occurrences(), 2);
+    assert_eq!(
+        report.deduction,
+        Deduction::NoSolution(vec!["x".to_string(), "y".to_string()])
+    );
+    // No ingredient is in all foods with x
+    let foods = Day21::parse(&b"a (contains x)\nb (contains x)\n"[..])?;
+    assert_eq!(deduce(&foods), Deduction::NoSolution(vec!["x".to_string()]));
+    assert_eq!(Day21::part1(&foods)?, 2);
+    assert!(Day21::part2(&foods).is_err());
+    Ok(())
+}
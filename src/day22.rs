@@ -0,0 +1,317 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use crate::Solver;
+use anyhow::{anyhow, Result};
+use log::debug;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::Write;
+use std::io::BufRead;
+
+// Parser: //
+
+pub mod parser {
+    use nom::{
+        branch::alt, bytes::complete::tag, character::complete::char, character::complete::digit1,
+        combinator::all_consuming, combinator::map_res, multi::separated_list1, IResult,
+    };
+    use std::collections::VecDeque;
+    use std::str::FromStr;
+
+    pub fn card(input: &str) -> IResult<&str, i32> {
+        map_res(digit1, i32::from_str)(input)
+    }
+
+    pub fn deck(input: &str) -> IResult<&str, VecDeque<i32>> {
+        let (input, _) = tag("Player ")(input)?;
+        let (input, _) = alt((tag("1"), tag("2")))(input)?;
+        let (input, _) = tag(":\n")(input)?;
+        let (input, cards) = separated_list1(char('\n'), card)(input)?;
+        let (input, _) = char('\n')(input)?;
+        Ok((input, cards.into_iter().collect()))
+    }
+
+    pub fn decks2(input: &str) -> IResult<&str, [VecDeque<i32>; 2]> {
+        let (input, deck1) = deck(input)?;
+        let (input, _) = char('\n')(input)?;
+        let (input, deck2) = deck(input)?;
+        Ok((input, [deck1, deck2]))
+    }
+
+    pub fn allinput(input: &str) -> IResult<&str, [VecDeque<i32>; 2]> {
+        all_consuming(decks2)(input)
+    }
+}
+
+// Games //
+
+pub fn score(deck: &VecDeque<i32>) -> Option<i64> {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .try_fold(0_i64, |acc, (i, &v)| {
+            let pos = i64::try_from(i).ok()?.checked_add(1)?;
+            acc.checked_add(i64::from(v).checked_mul(pos)?)
+        })
+}
+
+/// Statistics of a game, including its sub-games
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CombatStats {
+    /// Rounds played in each game, in the order the games started
+    pub rounds: Vec<usize>,
+    /// How deep the sub-games went; 0 if there were none
+    pub max_depth: usize,
+}
+
+impl CombatStats {
+    pub fn games(&self) -> usize {
+        self.rounds.len()
+    }
+
+    pub fn subgames(&self) -> usize {
+        self.games().saturating_sub(1)
+    }
+}
+
+/// The configuration of the current state of the decks, used to detect
+/// repeated rounds: the size of the first deck followed by the cards of
+/// both decks
+fn deck_key(decks: &[VecDeque<i32>; 2]) -> Vec<i32> {
+    let mut key = Vec::with_capacity(1 + decks[0].len() + decks[1].len());
+    key.push(decks[0].len() as i32);
+    key.extend(decks[0].iter().chain(decks[1].iter()));
+    key
+}
+
+fn deck_str(deck: &VecDeque<i32>) -> String {
+    deck.iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Plays Combat, regular or recursive, optionally writing a replay log
+/// in the format used by the puzzle
+#[derive(Debug, Default)]
+pub struct Combat {
+    pub recursive: bool,
+    pub stats: CombatStats,
+    log: Option<String>,
+}
+
+impl Combat {
+    pub fn new(recursive: bool) -> Combat {
+        Combat {
+            recursive,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_log(mut self) -> Combat {
+        self.log = Some(String::new());
+        self
+    }
+
+    pub fn log(&self) -> Option<&str> {
+        self.log.as_deref()
+    }
+
+    fn logln(&mut self, args: fmt::Arguments) {
+        if let Some(log) = &mut self.log {
+            log.write_fmt(args).unwrap();
+            log.push('\n');
+        }
+    }
+
+    /// Play a game, leaving the cards in decks; returns the winner
+    pub fn play(&mut self, decks: &mut [VecDeque<i32>; 2]) -> usize {
+        let winner = self.game(decks, 0);
+        self.logln(format_args!("== Post-game results =="));
+        for (i, deck) in decks.iter().enumerate() {
+            self.logln(format_args!("Player {}'s deck: {}", i + 1, deck_str(deck)));
+        }
+        winner
+    }
+
+    fn game(&mut self, decks: &mut [VecDeque<i32>; 2], depth: usize) -> usize {
+        self.stats.rounds.push(0);
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        let gameid = self.stats.rounds.len();
+        if self.recursive {
+            self.logln(format_args!("=== Game {} ===\n", gameid));
+        }
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        let winner = loop {
+            if decks[0].is_empty() || decks[1].is_empty() {
+                break if decks[0].is_empty() { 1 } else { 0 };
+            }
+            if self.recursive && !seen.insert(deck_key(decks)) {
+                break 0;
+            }
+            round += 1;
+            self.stats.rounds[gameid - 1] = round;
+            if self.recursive {
+                self.logln(format_args!("-- Round {} (Game {}) --", round, gameid));
+            } else {
+                self.logln(format_args!("-- Round {} --", round));
+            }
+            for (i, deck) in decks.iter().enumerate() {
+                self.logln(format_args!("Player {}'s deck: {}", i + 1, deck_str(deck)));
+            }
+            let cards = [decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap()];
+            self.logln(format_args!("Player 1 plays: {}", cards[0]));
+            self.logln(format_args!("Player 2 plays: {}", cards[1]));
+            let rwinner = if self.recursive
+                && decks[0].len() as i32 >= cards[0]
+                && decks[1].len() as i32 >= cards[1]
+            {
+                self.logln(format_args!(
+                    "Playing a sub-game to determine the winner...\n"
+                ));
+                let mut subdecks = [0, 1].map(|i| {
+                    decks[i]
+                        .iter()
+                        .take(cards[i] as usize)
+                        .copied()
+                        .collect::<VecDeque<_>>()
+                });
+                let rwinner = self.game(&mut subdecks, depth + 1);
+                self.logln(format_args!("...back to game {}.", gameid));
+                rwinner
+            } else if cards[0] > cards[1] {
+                0
+            } else {
+                1
+            };
+            if self.recursive {
+                self.logln(format_args!(
+                    "Player {} wins round {} of game {}!\n",
+                    rwinner + 1,
+                    round,
+                    gameid
+                ));
+            } else {
+                self.logln(format_args!("Player {} wins the round!\n", rwinner + 1));
+            }
+            decks[rwinner].push_back(cards[rwinner]);
+            decks[rwinner].push_back(cards[1 - rwinner]);
+        };
+        if self.recursive {
+            self.logln(format_args!(
+                "The winner of game {} is player {}!\n",
+                gameid,
+                winner + 1
+            ));
+        }
+        winner
+    }
+}
+
+// Solver //
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input = [VecDeque<i32>; 2];
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(mut bufin: impl BufRead) -> Result<Self::Input> {
+        let mut input = String::default();
+        bufin.read_to_string(&mut input)?;
+        Ok(parser::allinput(&input)
+            .map_err(|e| ParseError::from_nom(22, &input, e))?
+            .1)
+    }
+
+    fn part1(decks0: &Self::Input) -> Result<Self::Answer1> {
+        let mut decks = decks0.clone();
+        let winner = Combat::new(false).play(&mut decks);
+        score(&decks[winner]).ok_or_else(|| anyhow!("score overflow"))
+    }
+
+    fn part2(decks0: &Self::Input) -> Result<Self::Answer2> {
+        let mut decks = decks0.clone();
+        let mut combat = Combat::new(true);
+        let winner = combat.play(&mut decks);
+        debug!(
+            "{} sub-games, max depth {}",
+            combat.stats.subgames(),
+            combat.stats.max_depth
+        );
+        score(&decks[winner]).ok_or_else(|| anyhow!("score overflow"))
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<i64> {
+    Day22::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<i64> {
+    Day22::run2(bufin)
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    let input: &[u8] = b"Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n";
+    assert_eq!(solve_a(input)?, 306);
+    Ok(())
+}
+
+#[test]
+fn test1_b() -> Result<()> {
+    let input: &[u8] = b"Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n";
+    assert_eq!(solve_b(input)?, 291);
+    Ok(())
+}
+
+#[test]
+fn test_replay() -> Result<()> {
+    let input: &[u8] = b"Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n";
+    let decks0 = Day22::parse(input)?;
+    let mut decks = decks0.clone();
+    let mut combat = Combat::new(false).with_log();
+    assert_eq!(combat.play(&mut decks), 1);
+    assert_eq!(combat.stats.rounds, vec![29]);
+    let log = combat.log().unwrap();
+    assert!(log.starts_with("-- Round 1 --\nPlayer 1's deck: 9, 2, 6, 3, 1\nPlayer 2's deck: 5, 8, 4, 7, 10\nPlayer 1 plays: 9\nPlayer 2 plays: 5\nPlayer 1 wins the round!\n\n-- Round 2 --\n"));
+    assert!(log.ends_with("== Post-game results ==\nPlayer 1's deck: \nPlayer 2's deck: 3, 2, 10, 6, 8, 5, 9, 4, 7, 1\n"));
+    let mut decks = decks0;
+    let mut combat = Combat::new(true).with_log();
+    assert_eq!(combat.play(&mut decks), 1);
+    assert_eq!(combat.stats.rounds[0], 17);
+    assert_eq!(combat.stats.subgames(), 4);
+    assert_eq!(combat.stats.max_depth, 2);
+    let log = combat.log().unwrap();
+    assert!(log
+        .starts_with("=== Game 1 ===\n\n-- Round 1 (Game 1) --\nPlayer 1's deck: 9, 2, 6, 3, 1\n"));
+    assert!(log.contains("Player 1 plays: 4\nPlayer 2 plays: 3\nPlaying a sub-game to determine the winner...\n\n=== Game 2 ===\n\n-- Round 1 (Game 2) --\nPlayer 1's deck: 9, 8, 5, 2\nPlayer 2's deck: 10, 1, 7\n"));
+    assert!(log.contains("The winner of game 2 is player 2!\n\n...back to game 1.\nPlayer 2 wins round 9 of game 1!\n\n"));
+    assert!(log.ends_with("Player 2's deck: 7, 5, 6, 2, 4, 1, 10, 8, 9, 3\n"));
+    Ok(())
+}
+
+#[test]
+fn test_loop() -> Result<()> {
+    let input: &[u8] = b"Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n";
+    let mut decks = Day22::parse(input)?;
+    let mut combat = Combat::new(true);
+    assert_eq!(combat.play(&mut decks), 0);
+    assert_eq!(combat.stats.games(), 1);
+    // Cards that don't fit are parse errors
+    let input: &[u8] = b"Player 1:\n99999999999\n\nPlayer 2:\n2\n";
+    assert!(Day22::parse(input).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_score_large() {
+    let deck = VecDeque::from([i32::MAX, i32::MAX]);
+    assert_eq!(score(&deck), Some(3 * i64::from(i32::MAX)));
+}
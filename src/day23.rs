@@ -0,0 +1,302 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::Solver;
+use anyhow::{ensure, Result};
+use log::info;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::BufRead;
+
+// Parser: //
+
+pub mod parser {
+    use crate::ParseError;
+    use anyhow::Result;
+    use nom::{
+        character::complete::char, character::complete::one_of, combinator::all_consuming,
+        combinator::map, multi::many1, IResult,
+    };
+    use std::collections::VecDeque;
+    use std::io::BufRead;
+
+    pub fn cup(input: &str) -> IResult<&str, u8> {
+        let (input, cup) = map(one_of("0123456789"), |c| c.to_digit(10).unwrap())(input)?;
+        Ok((input, cup as u8))
+    }
+
+    pub fn cups(input: &str) -> IResult<&str, VecDeque<u8>> {
+        let (input, cups) = many1(cup)(input)?;
+        let (input, _) = char('\n')(input)?;
+        Ok((input, cups.into_iter().collect()))
+    }
+
+    pub fn allinput(input: &str) -> IResult<&str, VecDeque<u8>> {
+        all_consuming(cups)(input)
+    }
+
+    pub fn parse(mut bufin: impl BufRead) -> Result<VecDeque<u8>> {
+        let mut input = String::default();
+        bufin.read_to_string(&mut input)?;
+        Ok(allinput(&input)
+            .map_err(|e| ParseError::from_nom(23, &input, e))?
+            .1)
+    }
+}
+
+// CupCircle //
+
+/// The cups in a circle, stored as an array where each label indexes the
+/// label of the next cup; labels go from 1 to the number of cups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CupCircle {
+    next: Vec<u32>,
+    current: u32,
+    pickup: usize,
+    picked: Vec<u32>,
+}
+
+/// The state of the circle, starting at the current cup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub cups: Vec<usize>,
+}
+
+impl Snapshot {
+    pub fn current(&self) -> usize {
+        self.cups[0]
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.cups[0])?;
+        for cup in &self.cups[1..] {
+            write!(f, " {}", cup)?;
+        }
+        Ok(())
+    }
+}
+
+impl CupCircle {
+    /// Place the given cups, followed by the missing labels in order up to
+    /// total; each move picks up `pickup` cups
+    pub fn new(start: &[usize], total: usize, pickup: usize) -> Result<CupCircle> {
+        ensure!(!start.is_empty(), "no cups given");
+        ensure!(start.len() <= total, "more than {} cups given", total);
+        ensure!(
+            pickup + 1 < total,
+            "can't pick up {} of {} cups and still have a destination",
+            pickup,
+            total
+        );
+        let mut seen = vec![false; total + 1];
+        for &cup in start {
+            ensure!((1..=total).contains(&cup), "invalid cup {}", cup);
+            ensure!(!seen[cup], "repeated cup {}", cup);
+            seen[cup] = true;
+        }
+        let order = start
+            .iter()
+            .copied()
+            .chain((1..=total).filter(|&c| !seen[c]))
+            .collect::<Vec<_>>();
+        let mut next = vec![0; total + 1];
+        for (i, &cup) in order.iter().enumerate() {
+            next[cup] = order[(i + 1) % total] as u32;
+        }
+        Ok(CupCircle {
+            next,
+            current: start[0] as u32,
+            pickup,
+            picked: Vec::with_capacity(pickup),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn current(&self) -> usize {
+        self.current as usize
+    }
+
+    /// Do a single move
+    pub fn step(&mut self) {
+        let total = self.len() as u32;
+        let vcurr = self.current;
+        self.picked.clear();
+        let mut v = self.next[vcurr as usize];
+        for _ in 0..self.pickup {
+            self.picked.push(v);
+            v = self.next[v as usize];
+        }
+        self.next[vcurr as usize] = v;
+        let mut vdest = vcurr;
+        loop {
+            vdest = if vdest == 1 { total } else { vdest - 1 };
+            if !self.picked.contains(&vdest) {
+                break;
+            }
+        }
+        if let (Some(&first), Some(&last)) = (self.picked.first(), self.picked.last()) {
+            self.next[last as usize] = self.next[vdest as usize];
+            self.next[vdest as usize] = first;
+        }
+        self.current = self.next[vcurr as usize];
+    }
+
+    pub fn run(&mut self, moves: usize) {
+        for _ in 0..moves {
+            self.step();
+        }
+    }
+
+    /// The cups after the given one, going around once, without it
+    pub fn after(&self, cup: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut v = cup;
+        (1..self.len()).map(move |_| {
+            v = self.next[v] as usize;
+            v
+        })
+    }
+
+    /// All cups, going around once starting at the given one
+    pub fn iter_from(&self, cup: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(cup).chain(self.after(cup))
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cups: self.iter_from(self.current()).collect(),
+        }
+    }
+
+    /// Do the given number of moves, yielding the state after each one
+    pub fn moves(&mut self, moves: usize) -> impl Iterator<Item = Snapshot> + '_ {
+        (0..moves).map(move |_| {
+            self.step();
+            self.snapshot()
+        })
+    }
+
+    /// The labels after cup 1, as in the answer of part 1
+    pub fn labels(&self) -> String {
+        self.after(1).map(|c| c.to_string()).collect()
+    }
+}
+
+pub fn cups_usize(cups: &VecDeque<u8>) -> Vec<usize> {
+    cups.iter().map(|&c| c as usize).collect()
+}
+
+// Solver //
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input = VecDeque<u8>;
+    type Answer1 = String;
+    type Answer2 = u64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        parser::parse(bufin)
+    }
+
+    fn part1(cups: &Self::Input) -> Result<Self::Answer1> {
+        let mut circle = CupCircle::new(&cups_usize(cups), cups.len(), 3)?;
+        circle.run(100);
+        Ok(circle.labels())
+    }
+
+    fn part2(cups: &Self::Input) -> Result<Self::Answer2> {
+        let mut circle = CupCircle::new(&cups_usize(cups), 1_000_000, 3)?;
+        circle.run(10_000_000);
+        let stars = circle.after(1).take(2).collect::<Vec<_>>();
+        info!("{} * {}", stars[0], stars[1]);
+        Ok(stars[0] as u64 * stars[1] as u64)
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<String> {
+    Day23::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<u64> {
+    Day23::run2(bufin)
+}
+
+#[test]
+fn test0_a() -> Result<()> {
+    let input: &[u8] = b"389125467\n";
+    let cups = cups_usize(&parser::parse(input)?);
+    let mut circle = CupCircle::new(&cups, cups.len(), 3)?;
+    let last = circle.moves(10).last().unwrap();
+    assert_eq!(last.to_string(), "(8) 3 7 4 1 9 2 6 5");
+    assert_eq!(circle.labels(), "92658374");
+    Ok(())
+}
+
+#[test]
+fn test1_a() -> Result<()> {
+    let input: &[u8] = b"389125467\n";
+    assert_eq!(solve_a(input)?, "67384529");
+    Ok(())
+}
+
+#[test]
+fn test2_a() -> Result<()> {
+    let input: &[u8] = b"389125467\n";
+    let cups = cups_usize(&parser::parse(input)?);
+    let mut circle = CupCircle::new(&cups, cups.len(), 3)?;
+    let expected = [
+        ("54673289", 2),
+        ("32546789", 5),
+        ("34672589", 8),
+        ("32584679", 4),
+        ("36792584", 1),
+        ("93672584", 9),
+        ("92583674", 2),
+        ("58392674", 6),
+        ("83926574", 5),
+        ("92658374", 8),
+    ];
+    let mut snapshots = vec![];
+    for (labels, current) in expected {
+        circle.step();
+        assert_eq!(circle.labels(), labels);
+        assert_eq!(circle.current(), current);
+        snapshots.push(circle.snapshot());
+    }
+    assert_eq!(snapshots[1].to_string(), "(5) 4 6 7 8 9 1 3 2");
+    assert_eq!(snapshots[9].current(), 8);
+    assert_eq!(circle.labels(), "92658374");
+    assert_eq!(
+        circle.iter_from(1).collect::<Vec<_>>(),
+        vec![1, 9, 2, 6, 5, 8, 3, 7, 4]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_variants() -> Result<()> {
+    assert!(CupCircle::new(&[1, 1], 9, 3).is_err());
+    assert!(CupCircle::new(&[10], 9, 3).is_err());
+    assert!(CupCircle::new(&[1, 2, 3], 3, 2).is_err());
+    let mut circle = CupCircle::new(&[1, 2, 3], 3, 1)?;
+    let snapshots = circle.moves(2).map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(snapshots, vec!["(3) 2 1", "(1) 2 3"]);
+    Ok(())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    let input: &[u8] = b"389125467\n";
+    assert_eq!(solve_b(input)?, 149245887792);
+    Ok(())
+}
@@ -2,60 +2,19 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-use anyhow::{anyhow, Result};
+use crate::conway::Rules;
+use crate::hex::{self, Cube, Dir};
+use crate::Solver;
+use anyhow::Result;
 use std::collections::BTreeSet;
-use std::io::{stdin, BufRead};
-use std::ops;
-use std::str::FromStr;
-
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Coord {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32,
-}
-
-#[derive(Debug, Clone, Copy, Default)]
-pub struct Dir {
-    pub x: i8,
-    pub y: i8,
-    pub z: i8,
-}
-
-impl Dir {
-    pub fn new(x: i8, y: i8, z: i8) -> Dir {
-        Dir { x, y, z }
-    }
-}
-
-impl FromStr for Dir {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "ne" => Dir::new(1, 0, -1),
-            "e" => Dir::new(1, -1, 0),
-            "se" => Dir::new(0, -1, 1),
-            "sw" => Dir::new(-1, 0, 1),
-            "w" => Dir::new(-1, 1, 0),
-            "nw" => Dir::new(0, 1, -1),
-            _ => return Err(anyhow!("invalid Dir {}", s)),
-        })
-    }
-}
-
-impl ops::AddAssign<&Dir> for Coord {
-    fn add_assign(&mut self, dir: &Dir) {
-        self.x += dir.x as i32;
-        self.y += dir.y as i32;
-        self.z += dir.z as i32;
-    }
-}
+use std::io::BufRead;
 
 // Parser: //
 
 pub mod parser {
-    use super::Dir;
-    use anyhow::{anyhow, Result};
+    use crate::hex::Dir;
+    use crate::ParseError;
+    use anyhow::Result;
     use nom::{
         branch::alt, bytes::complete::tag, character::complete::char, combinator::all_consuming,
         combinator::map, multi::many1, IResult,
@@ -94,19 +53,19 @@ pub mod parser {
         let mut input = String::default();
         bufin.read_to_string(&mut input)?;
         Ok(allinput(&input)
-            .map_err(|e| anyhow!("error reading input: {:?}", e))?
+            .map_err(|e| ParseError::from_nom(24, &input, e))?
             .1)
     }
 }
 
 // Process, etc //
 
-fn process(bufin: impl BufRead) -> Result<usize> {
+/// Flip the tiles reached by each path, returning the black ones
+pub fn initial_blacks(paths: &[Vec<Dir>]) -> BTreeSet<Cube> {
     let mut blacks = BTreeSet::new();
-    let paths = parser::parse(bufin)?;
-    for path in &paths {
-        let mut h = Coord::default();
-        for dir in path {
+    for path in paths {
+        let mut h = Cube::default();
+        for &dir in path {
             h += dir;
         }
         if blacks.contains(&h) {
@@ -115,18 +74,52 @@ fn process(bufin: impl BufRead) -> Result<usize> {
             blacks.insert(h);
         }
     }
-    Ok(blacks.len())
+    blacks
+}
+
+// Solver //
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Input = Vec<Vec<Dir>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        parser::parse(bufin)
+    }
+
+    fn part1(paths: &Self::Input) -> Result<Self::Answer1> {
+        Ok(initial_blacks(paths).len())
+    }
+
+    fn part2(paths: &Self::Input) -> Result<Self::Answer2> {
+        // A black tile with zero or more than 2 black neighbors is
+        // flipped to white; a white tile with exactly 2 is flipped to black
+        let rules = Rules::new(&[2], &[1, 2]);
+        Ok(hex::life(&initial_blacks(paths), &rules, 100).len())
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<usize> {
+    Day24::run1(bufin)
+}
+
+pub fn solve_b(bufin: impl BufRead) -> Result<usize> {
+    Day24::run2(bufin)
 }
 
 #[test]
-fn test0() -> Result<()> {
-    eprintln!();
+fn test0_a() -> Result<()> {
     let input: &[u8] = b"sesenwnenenewseeswwswswwnenewsewsw\nneeenesenwnwwswnenewnwwsewnenwseswesw\nseswneswswsenwwnwse\nnwnwneseeswswnenewneswwnewseswneseene\nswweswneswnenwsewnwneneseenw\neesenwseswswnenwswnwnwsewwnwsene\nsewnenenenesenwsewnenwwwse\nwenwwweseeeweswwwnwwe\nwsweesenenewnwwnwsenewsenwwsesesenwne\nneeswseenwwswnwswswnw\nnenwswwsewswnenenewsenwsenwnesesenew\nenewnwewneswsewnwswenweswnenwsenwsw\nsweneswneswneneenwnewenewwneswswnese\nswwesenesewenwneswnwwneseswwne\nenesenwswwswneneswsenwnewswseenwsese\nwnwnesenesenenwwnenwsewesewsesesew\nnenewswnwewswnenesenwnesewesw\neneswnwswnwsenenwnwnwwseeswneewsenese\nneswnwewnwnwseenwseesewsenwsweewe\nwseweeenwnesenwwwswnew\n";
-    assert_eq!(process(input)?, 10);
+    assert_eq!(solve_a(input)?, 10);
     Ok(())
 }
 
-fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+#[test]
+fn test0_b() -> Result<()> {
+    let input: &[u8] = b"sesenwnenenewseeswwswswwnenewsewsw\nneeenesenwnwwswnenewnwwsewnenwseswesw\nseswneswswsenwwnwse\nnwnwneseeswswnenewneswwnewseswneseene\nswweswneswnenwsewnwneneseenw\neesenwseswswnenwswnwnwsewwnwsene\nsewnenenenesenwsewnenwwwse\nwenwwweseeeweswwwnwwe\nwsweesenenewnwwnwsenewsenwwsesesenwne\nneeswseenwwswnwswswnw\nnenwswwsewswnenenewsenwsenwnesesenew\nenewnwewneswsewnwswenweswnenwsenwsw\nsweneswneswneneenwnewenewwneswswnese\nswwesenesewenwneswnwwneseswwne\nenesenwswwswneneswsenwnewswseenwsese\nwnwnesenesenenwwnenwsewesewsesesew\nnenewswnwewswnenesenwnesewesw\neneswnwswnwsenenwnwnwwseeswneewsenese\nneswnwewnwnwseenwseesewsenwsweewe\nwseweeenwnesenwwwswnew\n";
+    assert_eq!(solve_b(input)?, 2208);
     Ok(())
 }
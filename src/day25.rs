@@ -0,0 +1,197 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::modular::{discrete_log, gcd, pow_mod};
+use crate::Solver;
+use anyhow::{anyhow, bail, ensure, Result};
+use log::info;
+use std::io::BufRead;
+
+// Parser: //
+
+pub mod parser {
+    use crate::ParseError;
+    use anyhow::Result;
+    use nom::{
+        character::complete::char, character::complete::digit1, combinator::all_consuming,
+        combinator::map_res, sequence::pair, IResult,
+    };
+    use std::io::BufRead;
+
+    pub fn pk(input: &str) -> IResult<&str, u64> {
+        let (input, pk) = map_res(digit1, |s: &str| s.parse::<u64>())(input)?;
+        let (input, _) = char('\n')(input)?;
+        Ok((input, pk))
+    }
+
+    pub fn pks(input: &str) -> IResult<&str, [u64; 2]> {
+        let (input, (cardpk, doorpk)) = pair(pk, pk)(input)?;
+        Ok((input, [cardpk, doorpk]))
+    }
+
+    pub fn allinput(input: &str) -> IResult<&str, [u64; 2]> {
+        all_consuming(pks)(input)
+    }
+
+    pub fn parse(mut bufin: impl BufRead) -> Result<[u64; 2]> {
+        let mut input = String::default();
+        bufin.read_to_string(&mut input)?;
+        Ok(allinput(&input)
+            .map_err(|e| ParseError::from_nom(25, &input, e))?
+            .1)
+    }
+}
+
+// Handshake //
+
+/// The card and the door each transform the subject number by a secret
+/// loop size to get their public keys, and then the public key of the
+/// other by their loop size to get the same encryption key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    pub subject: u64,
+    pub modulus: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPair {
+    pub loop_size: u64,
+    pub public_key: u64,
+}
+
+impl Default for Handshake {
+    fn default() -> Handshake {
+        Handshake {
+            subject: 7,
+            modulus: 20201227,
+        }
+    }
+}
+
+/// Mix the bits of a seed, from splitmix64
+fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl Handshake {
+    pub fn new(subject: u64, modulus: u64) -> Result<Handshake> {
+        ensure!(modulus > 1, "modulus {} is too small", modulus);
+        ensure!(
+            gcd(subject % modulus, modulus) == 1,
+            "subject {} is not coprime with modulus {}",
+            subject,
+            modulus
+        );
+        Ok(Handshake { subject, modulus })
+    }
+
+    pub fn transform(&self, subject: u64, loop_size: u64) -> u64 {
+        pow_mod(subject, loop_size, self.modulus)
+    }
+
+    pub fn key_pair(&self, loop_size: u64) -> KeyPair {
+        KeyPair {
+            loop_size,
+            public_key: self.transform(self.subject, loop_size),
+        }
+    }
+
+    /// Key pair with a loop size in 1..modulus picked from the seed
+    pub fn keygen(&self, seed: u64) -> KeyPair {
+        self.key_pair(1 + mix(seed) % (self.modulus - 1))
+    }
+
+    /// The smallest loop size that gives the public key
+    pub fn loop_size(&self, public_key: u64) -> Result<u64> {
+        discrete_log(self.subject, public_key, self.modulus)
+            .ok_or_else(|| anyhow!("no loop size gives the public key {}", public_key))
+    }
+
+    pub fn encryption_key(&self, own: &KeyPair, other_public_key: u64) -> u64 {
+        self.transform(other_public_key, own.loop_size)
+    }
+
+    /// Get the encryption key from the public keys alone, by finding a loop
+    /// size of the card
+    pub fn crack(&self, cardpk: u64, doorpk: u64) -> Result<u64> {
+        let card = KeyPair {
+            loop_size: self.loop_size(cardpk)?,
+            public_key: cardpk,
+        };
+        info!("card loop size {}", card.loop_size);
+        Ok(self.encryption_key(&card, doorpk))
+    }
+}
+
+// Solver //
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    /// The card and door public keys
+    type Input = [u64; 2];
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input> {
+        parser::parse(bufin)
+    }
+
+    fn part1(pks: &Self::Input) -> Result<Self::Answer1> {
+        let [cardpk, doorpk] = *pks;
+        info!("cardpk {}, doorpk {}", cardpk, doorpk);
+        Handshake::default().crack(cardpk, doorpk)
+    }
+
+    fn part2(_pks: &Self::Input) -> Result<Self::Answer2> {
+        bail!("day 25 has no second part")
+    }
+}
+
+pub fn solve_a(bufin: impl BufRead) -> Result<u64> {
+    Day25::run1(bufin)
+}
+
+#[test]
+fn test0() -> Result<()> {
+    let input: &[u8] = b"5764801\n17807724\n";
+    assert_eq!(solve_a(input)?, 14897079);
+    Ok(())
+}
+
+#[test]
+fn test_loop_size() -> Result<()> {
+    let handshake = Handshake::default();
+    assert_eq!(handshake.loop_size(5764801)?, 8);
+    assert_eq!(handshake.loop_size(17807724)?, 11);
+    Ok(())
+}
+
+#[test]
+fn test_transform() -> Result<()> {
+    let handshake = Handshake::default();
+    assert_eq!(handshake.transform(7, 8), 5764801);
+    assert_eq!(handshake.transform(7, 11), 17807724);
+    assert_eq!(handshake.transform(17807724, 8), 14897079);
+    Ok(())
+}
+
+#[test]
+fn test_keygen() -> Result<()> {
+    // A prime modulus about 10^12: linear loops would take hours
+    let handshake = Handshake::new(5, 1_000_000_000_039)?;
+    let card = handshake.keygen(1);
+    let door = handshake.keygen(2);
+    assert_ne!(card, door);
+    let key = handshake.encryption_key(&card, door.public_key);
+    assert_eq!(key, handshake.encryption_key(&door, card.public_key));
+    assert_eq!(handshake.crack(card.public_key, door.public_key)?, key);
+    assert!(Handshake::new(6, 20201226).is_err());
+    assert!(Handshake::new(7, 1).is_err());
+    assert!(parser::parse(&b"5764801\n"[..]).is_err());
+    Ok(())
+}
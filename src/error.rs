@@ -0,0 +1,167 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Error in a puzzle input, with the location of the failure.
+///
+/// Lines and columns start at 1. When displayed, the offending line is
+/// shown with a caret under the column where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    /// The line where the error is
+    pub text: String,
+    /// What the parser was expecting at the column
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, column: usize, text: &str, expected: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Error at the given byte offset of a multi-line input
+    pub fn at_offset(day: u32, input: &str, offset: usize, expected: &str) -> ParseError {
+        let offset = offset.min(input.len());
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError::new(
+            day,
+            input[..start].matches('\n').count() + 1,
+            input[start..offset].chars().count() + 1,
+            &input[start..end],
+            expected,
+        )
+    }
+
+    /// Error at the start of `rest`, which is the unparsed suffix of
+    /// `input`, like nom returns
+    pub fn at_rest(day: u32, input: &str, rest: &str, expected: &str) -> ParseError {
+        ParseError::at_offset(day, input, input.len() - rest.len(), expected)
+    }
+
+    /// Convert a nom error of a parser that was given `input`
+    pub fn from_nom(day: u32, input: &str, err: nom::Err<nom::error::Error<&str>>) -> ParseError {
+        match err {
+            nom::Err::Incomplete(_) => ParseError::at_offset(day, input, input.len(), "more input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                ParseError::at_rest(day, input, e.input, &nom_expected(e.code))
+            }
+        }
+    }
+
+    /// Move the error to the given line; used when the parser only sees
+    /// one line of the input
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+}
+
+fn nom_expected(kind: nom::error::ErrorKind) -> String {
+    use nom::error::ErrorKind;
+    match kind {
+        ErrorKind::Char => "a specific character",
+        ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::Tag => "a keyword",
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "a word",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::Many1 | ErrorKind::SeparatedList => "at least one item",
+        ErrorKind::Alt => "one of the alternatives",
+        ErrorKind::MapRes | ErrorKind::MapOpt => "a value that fits",
+        ErrorKind::Verify => "a valid value",
+        kind => return kind.description().to_lowercase(),
+    }
+    .to_string()
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>1$}", "^", self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a whole line with FromStr, pointing at its start on failure
+pub fn parse_line<T: FromStr>(day: u32, line: usize, text: &str) -> Result<T, ParseError> {
+    text.parse::<T>().map_err(|_| {
+        let expected = std::any::type_name::<T>();
+        let expected = expected.rsplit("::").next().unwrap_or(expected);
+        ParseError::new(day, line, 1, text, expected)
+    })
+}
+
+/// Parse a line with items separated by `sep`, pointing at the first one
+/// that fails
+pub fn parse_list<T: FromStr>(
+    day: u32,
+    line: usize,
+    text: &str,
+    sep: char,
+) -> Result<Vec<T>, ParseError> {
+    let mut column = 1;
+    let mut items = vec![];
+    for item in text.split(sep) {
+        items.push(parse_line(day, line, item).map_err(|e| ParseError {
+            column,
+            text: text.to_string(),
+            ..e
+        })?);
+        column += item.chars().count() + 1;
+    }
+    Ok(items)
+}
+
+#[test]
+fn test_at_offset() {
+    let e = ParseError::at_offset(8, "nop +0\nacc +1\nxyz +3\n", 14, "acc, jmp or nop");
+    assert_eq!(e, ParseError::new(8, 3, 1, "xyz +3", "acc, jmp or nop"));
+    let e = ParseError::at_offset(1, "12\n3x\n", 4, "digit");
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "3x"));
+    let e = ParseError::at_offset(1, "12", 2, "newline");
+    assert_eq!((e.line, e.column, e.text.as_str()), (1, 3, "12"));
+}
+
+#[test]
+fn test_display() {
+    let e = ParseError::new(18, 2, 5, "1 + x", "a number");
+    assert_eq!(
+        e.to_string(),
+        "day 18, line 2, column 5: expected a number\n  1 + x\n      ^"
+    );
+}
+
+#[test]
+fn test_parse_line() {
+    assert_eq!(parse_line::<i64>(1, 4, "-12"), Ok(-12));
+    assert_eq!(
+        parse_line::<i64>(1, 4, "1x"),
+        Err(ParseError::new(1, 4, 1, "1x", "i64"))
+    );
+    assert_eq!(parse_list::<u8>(15, 1, "0,3,6", ','), Ok(vec![0, 3, 6]));
+    assert_eq!(
+        parse_list::<u8>(15, 1, "0,3,,6", ','),
+        Err(ParseError::new(15, 1, 5, "0,3,,6", "u8"))
+    );
+}
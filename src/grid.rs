@@ -0,0 +1,576 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::io::BufRead;
+use std::ops;
+use std::str::FromStr;
+
+// Xy //
+
+/// Grid coordinates: x grows to the right, y grows downwards
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Xy {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Xy {
+    /// Orthogonal directions
+    pub const DIRS4: [Xy; 4] = [
+        Xy { x: 0, y: -1 },
+        Xy { x: 1, y: 0 },
+        Xy { x: 0, y: 1 },
+        Xy { x: -1, y: 0 },
+    ];
+
+    /// Orthogonal and diagonal directions
+    pub const DIRS8: [Xy; 8] = [
+        Xy { x: -1, y: -1 },
+        Xy { x: 0, y: -1 },
+        Xy { x: 1, y: -1 },
+        Xy { x: -1, y: 0 },
+        Xy { x: 1, y: 0 },
+        Xy { x: -1, y: 1 },
+        Xy { x: 0, y: 1 },
+        Xy { x: 1, y: 1 },
+    ];
+
+    pub fn new(x: i32, y: i32) -> Xy {
+        Xy { x, y }
+    }
+}
+
+impl ops::Add for Xy {
+    type Output = Xy;
+    fn add(self, other: Xy) -> Xy {
+        Xy::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl ops::Sub for Xy {
+    type Output = Xy;
+    fn sub(self, other: Xy) -> Xy {
+        Xy::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl ops::Mul<i32> for Xy {
+    type Output = Xy;
+    fn mul(self, k: i32) -> Xy {
+        Xy::new(self.x * k, self.y * k)
+    }
+}
+
+// Grid //
+
+/// Dense rectangular grid, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Build a grid from its rows, which must all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if let Some(y) = rows.iter().position(|r| r.len() != width) {
+            return Err(anyhow!(
+                "row {} has {} cells, expected {}",
+                y,
+                rows[y].len(),
+                width
+            ));
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse a character map, one row per line, converting each character
+    /// with `cell`; characters it rejects are reported as errors of `day`,
+    /// as are empty maps and lines after the blank lines that end the map
+    pub fn parse(
+        day: u32,
+        bufin: impl BufRead,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>> {
+        let mut rows = vec![];
+        let mut ended = false;
+        for (i, line_opt) in bufin.lines().enumerate() {
+            let line = line_opt?;
+            if line.is_empty() {
+                ended = true;
+                continue;
+            }
+            if ended {
+                return Err(ParseError::new(day, i + 1, 1, &line, "end of input").into());
+            }
+            let mut row = vec![];
+            for (x, c) in line.chars().enumerate() {
+                row.push(
+                    cell(c).ok_or_else(|| {
+                        ParseError::new(day, i + 1, x + 1, &line, "a map character")
+                    })?,
+                );
+            }
+            if let Some(first) = rows.first().map(Vec::len) {
+                if row.len() != first {
+                    let expected = format!("a line with {} characters", first);
+                    let column = row.len().min(first) + 1;
+                    return Err(ParseError::new(day, i + 1, column, &line, &expected).into());
+                }
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(ParseError::new(day, 1, 1, "", "a map").into());
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, xy: Xy) -> bool {
+        xy.x >= 0 && xy.y >= 0 && (xy.x as usize) < self.width && (xy.y as usize) < self.height
+    }
+
+    fn index_of(&self, xy: Xy) -> Option<usize> {
+        if self.contains(xy) {
+            Some(xy.y as usize * self.width + xy.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, xy: Xy) -> Option<&T> {
+        self.index_of(xy).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, xy: Xy) -> Option<&mut T> {
+        self.index_of(xy).map(move |i| &mut self.cells[i])
+    }
+
+    /// Access as if the grid repeated itself in all directions; None only
+    /// if the grid is empty
+    pub fn get_wrapping(&self, xy: Xy) -> Option<&T> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let x = xy.x.rem_euclid(self.width as i32);
+        let y = xy.y.rem_euclid(self.height as i32);
+        self.get(Xy::new(x, y))
+    }
+
+    /// All coordinates, row by row
+    pub fn xys(&self) -> impl Iterator<Item = Xy> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Xy::new((i % width) as i32, (i / width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Xy, &T)> {
+        self.xys().zip(self.cells.iter())
+    }
+
+    fn neighbors<'a>(&'a self, xy: Xy, dirs: &'a [Xy]) -> impl Iterator<Item = Xy> + 'a {
+        dirs.iter()
+            .map(move |&d| xy + d)
+            .filter(move |&n| self.contains(n))
+    }
+
+    /// Orthogonal neighbors inside the grid
+    pub fn neighbors4(&self, xy: Xy) -> impl Iterator<Item = Xy> + '_ {
+        self.neighbors(xy, &Xy::DIRS4)
+    }
+
+    /// Orthogonal and diagonal neighbors inside the grid
+    pub fn neighbors8(&self, xy: Xy) -> impl Iterator<Item = Xy> + '_ {
+        self.neighbors(xy, &Xy::DIRS8)
+    }
+
+    /// The coordinates seen from xy looking towards dir, up to the border
+    pub fn ray(&self, xy: Xy, dir: Xy) -> impl Iterator<Item = Xy> + '_ {
+        (1..)
+            .map(move |k| xy + dir * k)
+            .take_while(move |&p| self.contains(p))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Zero-copy view of the grid in the given orientation
+    pub fn view(&self, orientation: Orientation) -> View<'_, T> {
+        View {
+            grid: self,
+            orientation,
+        }
+    }
+
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(Orientation::Rotate90).to_grid()
+    }
+
+    /// Mirror left to right
+    pub fn flip_h(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(Orientation::FlipH).to_grid()
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_v(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(Orientation::FlipV).to_grid()
+    }
+
+    /// Render the grid with one character per cell
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            s.extend(row.iter().map(&f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> ops::Index<Xy> for Grid<T> {
+    type Output = T;
+    fn index(&self, xy: Xy) -> &T {
+        let i = self
+            .index_of(xy)
+            .unwrap_or_else(|| panic!("{:?} outside {}x{} grid", xy, self.width, self.height));
+        &self.cells[i]
+    }
+}
+
+impl<T> ops::IndexMut<Xy> for Grid<T> {
+    fn index_mut(&mut self, xy: Xy) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        let i = self
+            .index_of(xy)
+            .unwrap_or_else(|| panic!("{:?} outside {}x{} grid", xy, width, height));
+        &mut self.cells[i]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(0, s.as_bytes(), Some)
+    }
+}
+
+// Orientation //
+
+/// The 8 symmetries of a rectangle (the dihedral group D4): the clockwise
+/// rotations, and the mirrorings across the vertical axis (FlipH), the
+/// horizontal axis (FlipV), the main diagonal (Transpose) and the other
+/// diagonal (AntiTranspose)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Orientation {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipH,
+    AntiTranspose,
+    FlipV,
+    Transpose,
+}
+
+impl Orientation {
+    /// The order matches `from_parts`: the rotations, then the same
+    /// rotations after FlipH
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipH,
+        Orientation::AntiTranspose,
+        Orientation::FlipV,
+        Orientation::Transpose,
+    ];
+
+    /// FlipH, if `flipped`, followed by `rotations` clockwise quarter turns
+    pub fn from_parts(flipped: bool, rotations: u8) -> Orientation {
+        Orientation::ALL[flipped as usize * 4 + rotations as usize % 4]
+    }
+
+    pub fn parts(self) -> (bool, u8) {
+        let i = self as u8;
+        (i >= 4, i % 4)
+    }
+
+    /// `self` followed by `next`
+    pub fn then(self, next: Orientation) -> Orientation {
+        let (f1, r1) = self.parts();
+        let (f2, r2) = next.parts();
+        // Mirroring after a rotation is the same as mirroring before the
+        // opposite rotation
+        let rotations = if f2 { r2 + 4 - r1 } else { r2 + r1 };
+        Orientation::from_parts(f1 != f2, rotations)
+    }
+
+    pub fn inverse(self) -> Orientation {
+        match self.parts() {
+            (false, r) => Orientation::from_parts(false, 4 - r),
+            // Mirrorings undo themselves
+            (true, _) => self,
+        }
+    }
+
+    /// Size of a width x height rectangle in this orientation
+    pub fn size(self, width: usize, height: usize) -> (usize, usize) {
+        if self.parts().1 % 2 == 1 {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Where a point of a width x height rectangle ends up
+    pub fn apply(self, xy: Xy, width: usize, height: usize) -> Xy {
+        let (w, h) = (width as i32, height as i32);
+        let (x, y) = (xy.x, xy.y);
+        let (x, y) = match self {
+            Orientation::Identity => (x, y),
+            Orientation::Rotate90 => (h - 1 - y, x),
+            Orientation::Rotate180 => (w - 1 - x, h - 1 - y),
+            Orientation::Rotate270 => (y, w - 1 - x),
+            Orientation::FlipH => (w - 1 - x, y),
+            Orientation::AntiTranspose => (h - 1 - y, w - 1 - x),
+            Orientation::FlipV => (x, h - 1 - y),
+            Orientation::Transpose => (y, x),
+        };
+        Xy::new(x, y)
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Orientation::Identity => "identity",
+            Orientation::Rotate90 => "rotate 90",
+            Orientation::Rotate180 => "rotate 180",
+            Orientation::Rotate270 => "rotate 270",
+            Orientation::FlipH => "flip left-right",
+            Orientation::AntiTranspose => "anti-transpose",
+            Orientation::FlipV => "flip top-bottom",
+            Orientation::Transpose => "transpose",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A grid seen in some orientation, without copying it
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    orientation: Orientation,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn width(&self) -> usize {
+        self.orientation.size(self.grid.width, self.grid.height).0
+    }
+
+    pub fn height(&self) -> usize {
+        self.orientation.size(self.grid.width, self.grid.height).1
+    }
+
+    /// The coordinates in the grid of a point of the view
+    pub fn source(&self, xy: Xy) -> Xy {
+        self.orientation
+            .inverse()
+            .apply(xy, self.width(), self.height())
+    }
+
+    pub fn get(&self, xy: Xy) -> Option<&'a T> {
+        let inside = xy.x >= 0
+            && xy.y >= 0
+            && (xy.x as usize) < self.width()
+            && (xy.y as usize) < self.height();
+        if inside {
+            self.grid.get(self.source(xy))
+        } else {
+            None
+        }
+    }
+
+    /// All coordinates of the view, row by row
+    pub fn xys(&self) -> impl Iterator<Item = Xy> {
+        let width = self.width();
+        (0..width * self.height()).map(move |i| Xy::new((i % width) as i32, (i / width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Xy, &'a T)> + '_ {
+        self.xys().map(move |xy| (xy, &self.grid[self.source(xy)]))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width(),
+            height: self.height(),
+            cells: self.iter().map(|(_, c)| c.clone()).collect(),
+        }
+    }
+}
+
+impl<'a, T> ops::Index<Xy> for View<'a, T> {
+    type Output = T;
+    fn index(&self, xy: Xy) -> &T {
+        self.get(xy)
+            .unwrap_or_else(|| panic!("{:?} outside {}x{} view", xy, self.width(), self.height()))
+    }
+}
+
+#[test]
+fn test_parse() -> Result<()> {
+    let grid = Grid::parse(0, &b"#.\n.#\n#.\n"[..], |c| Some(c == '#'))?;
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert!(grid[Xy::new(0, 2)]);
+    assert!(!grid[Xy::new(1, 2)]);
+    assert_eq!(grid.get(Xy::new(2, 0)), None);
+    assert_eq!(grid.get(Xy::new(0, -1)), None);
+    assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), "#.\n.#\n#.\n");
+    let err = Grid::parse(3, &b"#.\n.x\n"[..], |c| "#.".find(c)).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.day, err.line, err.column), (3, 2, 2));
+    assert!("##\n#\n".parse::<Grid<char>>().is_err());
+    // Blank lines can only be at the end
+    let grid = "#.\n.#\n\n\n".parse::<Grid<char>>()?;
+    assert_eq!(grid.height(), 2);
+    let err = Grid::parse(11, &b"#.\n\n.#\n"[..], Some).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, ".#"));
+    let err = Grid::parse(3, &b"\n"[..], Some).unwrap_err();
+    assert_eq!(err.downcast_ref::<ParseError>().unwrap().expected, "a map");
+    assert!("".parse::<Grid<char>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_wrapping() -> Result<()> {
+    let grid = "ab\ncd\n".parse::<Grid<char>>()?;
+    assert_eq!(grid.get_wrapping(Xy::new(2, 0)), Some(&'a'));
+    assert_eq!(grid.get_wrapping(Xy::new(-1, 3)), Some(&'d'));
+    assert_eq!(Grid::new(0, 0, 'a').get_wrapping(Xy::new(1, 1)), None);
+    Ok(())
+}
+
+#[test]
+fn test_neighbors() -> Result<()> {
+    let grid = "abc\ndef\nghi\n".parse::<Grid<char>>()?;
+    let neighs = |v: Vec<Xy>| v.into_iter().map(|xy| grid[xy]).collect::<String>();
+    assert_eq!(neighs(grid.neighbors4(Xy::new(0, 0)).collect()), "bd");
+    assert_eq!(neighs(grid.neighbors8(Xy::new(1, 1)).collect()), "abcdfghi");
+    assert_eq!(neighs(grid.neighbors8(Xy::new(2, 2)).collect()), "efh");
+    assert_eq!(
+        neighs(grid.ray(Xy::new(0, 0), Xy::new(1, 1)).collect()),
+        "ei"
+    );
+    assert_eq!(
+        neighs(grid.ray(Xy::new(2, 1), Xy::new(-1, 0)).collect()),
+        "ed"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rotate_flip() -> Result<()> {
+    let grid = "123\n456\n789\n".parse::<Grid<char>>()?;
+    assert_eq!(grid.flip_v().to_string(), "789\n456\n123\n");
+    assert_eq!(grid.flip_h().to_string(), "321\n654\n987\n");
+    assert_eq!(grid.rotate_right().to_string(), "741\n852\n963\n");
+    let grid = "12\n34\n56\n".parse::<Grid<char>>()?;
+    assert_eq!(grid.rotate_right().to_string(), "531\n642\n");
+    Ok(())
+}
+
+#[test]
+fn test_orientation() -> Result<()> {
+    let grid = "abc\ndef\n".parse::<Grid<char>>()?;
+    let view = |o: Orientation| grid.view(o).to_grid().to_string();
+    assert_eq!(view(Orientation::Rotate90), "da\neb\nfc\n");
+    assert_eq!(view(Orientation::Transpose), "ad\nbe\ncf\n");
+    assert_eq!(view(Orientation::AntiTranspose), "fc\neb\nda\n");
+    assert_eq!(view(Orientation::FlipV), "def\nabc\n");
+    for a in Orientation::ALL {
+        let (flipped, rotations) = a.parts();
+        let mut expected = if flipped { grid.flip_h() } else { grid.clone() };
+        for _ in 0..rotations {
+            expected = expected.rotate_right();
+        }
+        assert_eq!(grid.view(a).to_grid(), expected, "{}", a);
+        for (xy, &c) in grid.iter() {
+            assert_eq!(expected[a.apply(xy, 3, 2)], c);
+        }
+        assert_eq!(a.then(a.inverse()), Orientation::Identity);
+        for b in Orientation::ALL {
+            let twice = grid.view(a).to_grid().view(b).to_grid();
+            assert_eq!(grid.view(a.then(b)).to_grid(), twice, "{} then {}", a, b);
+        }
+    }
+    assert_eq!(
+        Orientation::FlipH.then(Orientation::Rotate90),
+        Orientation::AntiTranspose
+    );
+    Ok(())
+}
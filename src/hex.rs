@@ -0,0 +1,329 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::conway::Rules;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::ops;
+use std::str::FromStr;
+
+// Coordinates //
+
+// The grid has pointy-topped hexagons, so that rows are horizontal and
+// each tile has e and w neighbors, but no n or s.
+
+/// Cube coordinates; x + y + z is always 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cube {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Axial coordinates: q is the cube x and r is the cube z
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Axial {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Offset coordinates, with odd rows shoved right by half a tile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset {
+    pub col: i32,
+    pub row: i32,
+}
+
+impl Cube {
+    pub fn new(x: i32, y: i32, z: i32) -> Cube {
+        debug_assert_eq!(x + y + z, 0);
+        Cube { x, y, z }
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = Cube> {
+        Dir::ALL.iter().map(move |&d| self + d)
+    }
+
+    pub fn distance(self, other: Cube) -> i32 {
+        let d = self - other;
+        (d.x.abs() + d.y.abs() + d.z.abs()) / 2
+    }
+
+    /// The tiles at exactly `radius` steps, going around counterclockwise
+    /// starting from the one to the east
+    pub fn ring(self, radius: i32) -> Vec<Cube> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut ret = Vec::with_capacity(6 * radius as usize);
+        let mut c = self + Dir::E.delta() * radius;
+        // Walking from e, the sides of the ring go nw, w, sw, se, e, ne
+        for side in [Dir::Nw, Dir::W, Dir::Sw, Dir::Se, Dir::E, Dir::Ne] {
+            for _ in 0..radius {
+                ret.push(c);
+                c += side;
+            }
+        }
+        ret
+    }
+
+    /// All tiles up to `radius` steps, ring by ring
+    pub fn spiral(self, radius: i32) -> Vec<Cube> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    /// The tiles crossed by a straight line to `other`, both ends included
+    pub fn line(self, other: Cube) -> Vec<Cube> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self];
+        }
+        // Nudge the ends so that points over edges are rounded consistently
+        let a = (
+            self.x as f64 + 1e-6,
+            self.y as f64 + 1e-6,
+            self.z as f64 - 2e-6,
+        );
+        let b = (
+            other.x as f64 + 1e-6,
+            other.y as f64 + 1e-6,
+            other.z as f64 - 2e-6,
+        );
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Cube::round(
+                    a.0 + (b.0 - a.0) * t,
+                    a.1 + (b.1 - a.1) * t,
+                    a.2 + (b.2 - a.2) * t,
+                )
+            })
+            .collect()
+    }
+
+    /// The tile that contains the given fractional cube coordinates
+    pub fn round(x: f64, y: f64, z: f64) -> Cube {
+        let (mut rx, mut ry, mut rz) = (x.round(), y.round(), z.round());
+        let (dx, dy, dz) = ((rx - x).abs(), (ry - y).abs(), (rz - z).abs());
+        if dx > dy && dx > dz {
+            rx = -ry - rz;
+        } else if dy > dz {
+            ry = -rx - rz;
+        } else {
+            rz = -rx - ry;
+        }
+        Cube::new(rx as i32, ry as i32, rz as i32)
+    }
+}
+
+impl ops::Add for Cube {
+    type Output = Cube;
+    fn add(self, other: Cube) -> Cube {
+        Cube::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl ops::Add<Dir> for Cube {
+    type Output = Cube;
+    fn add(self, dir: Dir) -> Cube {
+        self + dir.delta()
+    }
+}
+
+impl ops::AddAssign<Dir> for Cube {
+    fn add_assign(&mut self, dir: Dir) {
+        *self = *self + dir;
+    }
+}
+
+impl ops::Sub for Cube {
+    type Output = Cube;
+    fn sub(self, other: Cube) -> Cube {
+        Cube::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl ops::Mul<i32> for Cube {
+    type Output = Cube;
+    fn mul(self, k: i32) -> Cube {
+        Cube::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+impl From<Axial> for Cube {
+    fn from(a: Axial) -> Cube {
+        Cube::new(a.q, -a.q - a.r, a.r)
+    }
+}
+
+impl From<Cube> for Axial {
+    fn from(c: Cube) -> Axial {
+        Axial { q: c.x, r: c.z }
+    }
+}
+
+impl From<Offset> for Axial {
+    fn from(o: Offset) -> Axial {
+        Axial {
+            q: o.col - (o.row - (o.row & 1)) / 2,
+            r: o.row,
+        }
+    }
+}
+
+impl From<Axial> for Offset {
+    fn from(a: Axial) -> Offset {
+        Offset {
+            col: a.q + (a.r - (a.r & 1)) / 2,
+            row: a.r,
+        }
+    }
+}
+
+impl From<Offset> for Cube {
+    fn from(o: Offset) -> Cube {
+        Axial::from(o).into()
+    }
+}
+
+impl From<Cube> for Offset {
+    fn from(c: Cube) -> Offset {
+        Axial::from(c).into()
+    }
+}
+
+// Dir //
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    E,
+    Se,
+    Sw,
+    W,
+    Nw,
+    Ne,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 6] = [Dir::E, Dir::Se, Dir::Sw, Dir::W, Dir::Nw, Dir::Ne];
+
+    pub fn delta(self) -> Cube {
+        match self {
+            Dir::E => Cube::new(1, -1, 0),
+            Dir::Se => Cube::new(0, -1, 1),
+            Dir::Sw => Cube::new(-1, 0, 1),
+            Dir::W => Cube::new(-1, 1, 0),
+            Dir::Nw => Cube::new(0, 1, -1),
+            Dir::Ne => Cube::new(1, 0, -1),
+        }
+    }
+}
+
+impl FromStr for Dir {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "e" => Dir::E,
+            "se" => Dir::Se,
+            "sw" => Dir::Sw,
+            "w" => Dir::W,
+            "nw" => Dir::Nw,
+            "ne" => Dir::Ne,
+            _ => return Err(anyhow!("invalid Dir {}", s)),
+        })
+    }
+}
+
+// Life //
+
+/// Run a single day of the flipping-tile life: black tiles are the active
+/// cells, and the rules count their black neighbors
+pub fn step(blacks: &BTreeSet<Cube>, rules: &Rules) -> BTreeSet<Cube> {
+    let mut neighs = BTreeMap::<Cube, usize>::new();
+    for b in blacks {
+        for n in b.neighbors() {
+            *neighs.entry(n).or_insert(0) += 1;
+        }
+    }
+    let mut next = blacks
+        .iter()
+        .filter(|b| !neighs.contains_key(b) && rules.next(true, 0))
+        .copied()
+        .collect::<BTreeSet<_>>();
+    next.extend(
+        neighs
+            .into_iter()
+            .filter(|(c, n)| rules.next(blacks.contains(c), *n))
+            .map(|(c, _)| c),
+    );
+    next
+}
+
+/// Run the given number of days, returning the final black tiles
+pub fn life(blacks: &BTreeSet<Cube>, rules: &Rules, days: usize) -> BTreeSet<Cube> {
+    let mut blacks = blacks.clone();
+    for _ in 0..days {
+        blacks = step(&blacks, rules);
+    }
+    blacks
+}
+
+#[test]
+fn test_conversions() {
+    for c in Cube::default().spiral(3) {
+        assert_eq!(c.x + c.y + c.z, 0);
+        assert_eq!(Cube::from(Axial::from(c)), c);
+        assert_eq!(Cube::from(Offset::from(c)), c);
+    }
+    let se = Cube::default() + Dir::Se;
+    assert_eq!(Offset::from(se), Offset { col: 0, row: 1 });
+    let sw = Cube::default() + Dir::Sw;
+    assert_eq!(Offset::from(sw), Offset { col: -1, row: 1 });
+}
+
+#[test]
+fn test_distance() {
+    let o = Cube::default();
+    assert_eq!(o.distance(o + Dir::E + Dir::E + Dir::Nw), 2);
+    assert_eq!(o.distance(o + Dir::E + Dir::W), 0);
+    assert!(o.neighbors().all(|n| n.distance(o) == 1));
+}
+
+#[test]
+fn test_ring_spiral() {
+    let o = Cube::default();
+    for r in 0..5 {
+        let ring = o.ring(r);
+        assert_eq!(ring.len(), if r == 0 { 1 } else { 6 * r as usize });
+        assert!(ring.iter().all(|c| c.distance(o) == r));
+        assert_eq!(ring.iter().collect::<BTreeSet<_>>().len(), ring.len());
+    }
+    assert_eq!(o.spiral(2).len(), 19);
+    assert_eq!(o.ring(1)[0], o + Dir::E);
+}
+
+#[test]
+fn test_line() {
+    let o = Cube::default();
+    let to = o + Dir::E.delta() * 3 + Dir::Se.delta() * 2;
+    let line = o.line(to);
+    assert_eq!(line.len(), 6);
+    assert_eq!((line[0], line[5]), (o, to));
+    for w in line.windows(2) {
+        assert_eq!(w[0].distance(w[1]), 1);
+    }
+}
+
+#[test]
+fn test_life() {
+    // Three in a row: with B2/S12 the middle one survives and two tiles
+    // at each side are born
+    let o = Cube::default();
+    let blacks = [o + Dir::W, o, o + Dir::E].iter().copied().collect();
+    let rules = Rules::new(&[2], &[1, 2]);
+    let next = life(&blacks, &rules, 1);
+    assert_eq!(next.len(), 7);
+    assert!(next.contains(&o));
+}
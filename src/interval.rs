@@ -0,0 +1,129 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+/// Set of integers stored as sorted, disjoint and non-adjacent inclusive
+/// ranges; open bounds are represented by i64::MIN and i64::MAX
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    pub fn ranges(&self) -> &[(i64, i64)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Add the inclusive range lo..=hi, merging it with the ranges it
+    /// overlaps or touches; empty ranges are ignored
+    pub fn insert(&mut self, lo: i64, hi: i64) {
+        if lo > hi {
+            return;
+        }
+        // First range that ends at or after lo - 1, and first that starts
+        // after hi + 1: everything in between gets merged
+        let start = self
+            .ranges
+            .partition_point(|&(_, h)| h < lo.saturating_sub(1));
+        let end = self
+            .ranges
+            .partition_point(|&(l, _)| l <= hi.saturating_add(1));
+        let (mut lo, mut hi) = (lo, hi);
+        if start < end {
+            lo = lo.min(self.ranges[start].0);
+            hi = hi.max(self.ranges[end - 1].1);
+        }
+        self.ranges.splice(start..end, [(lo, hi)]);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.ranges.partition_point(|&(_, h)| h < value);
+        i < self.ranges.len() && self.ranges[i].0 <= value
+    }
+
+    /// Union with another set
+    pub fn merge(&mut self, other: &IntervalSet) {
+        for &(lo, hi) in &other.ranges {
+            self.insert(lo, hi);
+        }
+    }
+}
+
+impl FromIterator<(i64, i64)> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = (i64, i64)>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for (lo, hi) in iter {
+            set.insert(lo, hi);
+        }
+        set
+    }
+}
+
+/// Uses the same syntax as the ticket rules: "1-3 or 5 or >= 10"; the
+/// rules can't have negative values, so only sets with open lower bounds
+/// or non-negative values can be parsed back
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &(lo, hi)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, " or ")?;
+            }
+            match (lo, hi) {
+                (i64::MIN, i64::MAX) => write!(f, "<= 0 or > 0")?,
+                (i64::MIN, hi) => write!(f, "<= {}", hi)?,
+                (lo, i64::MAX) => write!(f, ">= {}", lo)?,
+                (lo, hi) if lo == hi => write!(f, "{}", lo)?,
+                (lo, hi) => write!(f, "{}-{}", lo, hi)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_insert() {
+    let mut set = IntervalSet::new();
+    set.insert(10, 20);
+    set.insert(1, 3);
+    set.insert(30, 40);
+    assert_eq!(set.ranges(), &[(1, 3), (10, 20), (30, 40)]);
+    set.insert(4, 4);
+    assert_eq!(set.ranges(), &[(1, 4), (10, 20), (30, 40)]);
+    set.insert(15, 31);
+    assert_eq!(set.ranges(), &[(1, 4), (10, 40)]);
+    set.insert(7, 6);
+    assert_eq!(set.ranges(), &[(1, 4), (10, 40)]);
+    set.insert(0, i64::MAX);
+    assert_eq!(set.ranges(), &[(0, i64::MAX)]);
+}
+
+#[test]
+fn test_contains_merge() {
+    let mut set = [(1, 3), (5, 7)].into_iter().collect::<IntervalSet>();
+    assert!(!set.contains(0));
+    assert!(set.contains(1));
+    assert!(set.contains(3));
+    assert!(!set.contains(4));
+    assert!(set.contains(7));
+    assert!(!set.contains(8));
+    set.merge(&[(i64::MIN, 0), (4, 4)].into_iter().collect());
+    assert_eq!(set.ranges(), &[(i64::MIN, 7)]);
+    assert_eq!(set.to_string(), "<= 7");
+    let set = [(1, 1), (3, 5), (9, i64::MAX)]
+        .into_iter()
+        .collect::<IntervalSet>();
+    assert_eq!(set.to_string(), "1 or 3-5 or >= 9");
+    let set = [(i64::MIN, i64::MAX)].into_iter().collect::<IntervalSet>();
+    assert_eq!(set.to_string(), "<= 0 or > 0");
+}
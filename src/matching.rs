@@ -0,0 +1,219 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Assignment of distinct choices to items, where `cands[item]` has the
+//! indexes of the choices that fit the item
+
+/// Try to find an alternating path from item to a free choice, updating
+/// the matching if there is one
+fn augment(
+    cands: &[Vec<usize>],
+    item: usize,
+    choice_item: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &choice in &cands[item] {
+        if visited[choice] {
+            continue;
+        }
+        visited[choice] = true;
+        let free = match choice_item[choice] {
+            None => true,
+            Some(other) => augment(cands, other, choice_item, visited),
+        };
+        if free {
+            choice_item[choice] = Some(item);
+            return true;
+        }
+    }
+    false
+}
+
+/// Match every item to a distinct choice; on failure, return a set of
+/// items that have fewer candidate choices than members
+pub fn matching(cands: &[Vec<usize>], num_choices: usize) -> Result<Vec<usize>, Vec<usize>> {
+    let mut choice_item = vec![None; num_choices];
+    for item in 0..cands.len() {
+        let mut visited = vec![false; num_choices];
+        if !augment(cands, item, &mut choice_item, &mut visited) {
+            // The choices visited are all matched to items whose
+            // candidates were also visited: together with item, these
+            // items outnumber their choices
+            let mut items = (0..num_choices)
+                .filter(|&c| visited[c])
+                .filter_map(|c| choice_item[c])
+                .collect::<Vec<_>>();
+            items.push(item);
+            items.sort_unstable();
+            return Err(items);
+        }
+    }
+    let mut assignment = vec![0; cands.len()];
+    for (choice, item) in choice_item.iter().enumerate() {
+        if let Some(item) = item {
+            assignment[*item] = choice;
+        }
+    }
+    Ok(assignment)
+}
+
+/// Whether `assignment`, a complete matching, is the only one
+///
+/// Another one exists exactly when an item can switch to a free choice, or
+/// when items can switch choices in a cycle: item p points to item q when
+/// one of the other candidates of p is the choice of q.
+pub fn is_unique(cands: &[Vec<usize>], num_choices: usize, assignment: &[usize]) -> bool {
+    let mut choice_item = vec![None; num_choices];
+    for (item, &choice) in assignment.iter().enumerate() {
+        choice_item[choice] = Some(item);
+    }
+    let mut edges = vec![vec![]; cands.len()];
+    let mut indegree = vec![0; cands.len()];
+    for (item, item_cands) in cands.iter().enumerate() {
+        for &choice in item_cands {
+            if choice == assignment[item] {
+                continue;
+            }
+            match choice_item[choice] {
+                None => return false,
+                Some(other) => {
+                    edges[item].push(other);
+                    indegree[other] += 1;
+                }
+            }
+        }
+    }
+    // Remove items without incoming edges until only cycles are left
+    let mut ready = (0..cands.len())
+        .filter(|&i| indegree[i] == 0)
+        .collect::<Vec<_>>();
+    let mut removed = 0;
+    while let Some(item) = ready.pop() {
+        removed += 1;
+        for &other in &edges[item] {
+            indegree[other] -= 1;
+            if indegree[other] == 0 {
+                ready.push(other);
+            }
+        }
+    }
+    removed == cands.len()
+}
+
+/// Enumerate up to `limit` assignments of distinct choices to items
+///
+/// The search keeps a complete matching that agrees with the choices made
+/// so far, and only takes a choice if the matching can be repaired with a
+/// single alternating path; so every branch ends in an assignment, and the
+/// limit also bounds the work.
+pub fn assignments(cands: &[Vec<usize>], limit: usize) -> Vec<Vec<usize>> {
+    let num_choices = cands.iter().flatten().max().map_or(0, |m| m + 1);
+    let start = match matching(cands, num_choices) {
+        Ok(assignment) => assignment,
+        Err(_) => return vec![],
+    };
+    let mut choice_item = vec![None; num_choices];
+    for (item, &choice) in start.iter().enumerate() {
+        choice_item[choice] = Some(item);
+    }
+    // Items with fewer candidates first, to prune early
+    let mut order = (0..cands.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| cands[i].len());
+    let mut found = vec![];
+    let mut used = vec![false; num_choices];
+    assign_rec(cands, &order, &choice_item, &mut used, &mut found, limit);
+    found
+}
+
+fn assign_rec(
+    cands: &[Vec<usize>],
+    order: &[usize],
+    choice_item: &[Option<usize>],
+    used: &mut [bool],
+    found: &mut Vec<Vec<usize>>,
+    limit: usize,
+) {
+    if found.len() >= limit {
+        return;
+    }
+    let (&item, rest) = match order.split_first() {
+        None => {
+            let mut assignment = vec![0; cands.len()];
+            for (choice, item) in choice_item.iter().enumerate() {
+                if let Some(item) = item {
+                    assignment[*item] = choice;
+                }
+            }
+            found.push(assignment);
+            return;
+        }
+        Some(split) => split,
+    };
+    for &choice in &cands[item] {
+        if used[choice] {
+            continue;
+        }
+        let mut next = choice_item.to_vec();
+        let owner = next[choice];
+        if owner != Some(item) {
+            // Give the choice to item, and find another one for its owner
+            // among the choices that are not fixed
+            for c in next.iter_mut() {
+                if *c == Some(item) {
+                    *c = None;
+                }
+            }
+            next[choice] = Some(item);
+            if let Some(owner) = owner {
+                let mut visited = used.to_vec();
+                visited[choice] = true;
+                if !augment(cands, owner, &mut next, &mut visited) {
+                    continue;
+                }
+            }
+        }
+        used[choice] = true;
+        assign_rec(cands, rest, &next, used, found, limit);
+        used[choice] = false;
+    }
+}
+
+#[test]
+fn test_matching() {
+    let cands = vec![vec![0, 1], vec![0], vec![1, 2]];
+    assert_eq!(matching(&cands, 3), Ok(vec![1, 0, 2]));
+    assert_eq!(assignments(&cands, 10), vec![vec![1, 0, 2]]);
+    // Items 0 and 1 only fit choice 0
+    let cands = vec![vec![0], vec![0], vec![1, 2]];
+    assert_eq!(matching(&cands, 3), Err(vec![0, 1]));
+    assert!(assignments(&cands, 10).is_empty());
+    let cands = vec![vec![0, 1], vec![0, 1]];
+    assert_eq!(assignments(&cands, 10).len(), 2);
+    assert_eq!(assignments(&cands, 1).len(), 1);
+}
+
+#[test]
+fn test_unique() {
+    let cands = vec![vec![0, 1], vec![0], vec![1, 2]];
+    assert!(is_unique(&cands, 3, &[1, 0, 2]));
+    // Items 0 and 2 can swap
+    let cands = vec![vec![0, 2], vec![1], vec![0, 2]];
+    assert!(!is_unique(&cands, 3, &[0, 1, 2]));
+    // Item 0 can move to the unused choice 3
+    let cands = vec![vec![0, 3], vec![1], vec![2]];
+    assert!(!is_unique(&cands, 4, &[0, 1, 2]));
+    // Every item fits every choice: 30! assignments, and the listing
+    // stops at the limit quickly
+    let n = 30;
+    let cands = vec![(0..n).collect::<Vec<_>>(); n];
+    let assignment = matching(&cands, n).unwrap();
+    assert!(!is_unique(&cands, n, &assignment));
+    assert_eq!(assignments(&cands, 1000).len(), 1000);
+    // Dense, but with a single assignment deep in the search: item i fits
+    // choices i..n, and the last item only fits the last choice
+    let cands = (0..n).map(|i| (i..n).collect()).collect::<Vec<Vec<_>>>();
+    let assignment = matching(&cands, n).unwrap();
+    assert!(is_unique(&cands, n, &assignment));
+    assert_eq!(assignments(&cands, 1000), vec![(0..n).collect::<Vec<_>>()]);
+}
@@ -0,0 +1,106 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Arithmetic modulo any u64, with intermediate products in u128
+
+use std::collections::HashMap;
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// base^exp mod modulus, by repeated squaring
+pub fn pow_mod(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Smallest x such that base^x = target mod modulus, found with
+/// baby-step giant-step in O(sqrt(modulus)) time and memory
+///
+/// Returns None if there is no such x or if the base is not coprime with
+/// the modulus, which can't happen with a prime modulus and a nonzero base.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    let target = target % modulus;
+    if target == 1 % modulus {
+        return Some(0);
+    }
+    if gcd(base % modulus, modulus) != 1 {
+        return None;
+    }
+    let mut n = modulus.isqrt();
+    if n * n < modulus {
+        n += 1;
+    }
+    // Baby steps: target * base^i for i in 0..n, keeping the largest i
+    // of repeated values so that the x found below is the smallest
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut curr = target;
+    for i in 0..n {
+        baby.insert(curr, i);
+        curr = mul_mod(curr, base, modulus);
+    }
+    // Giant steps: base^(n*j) = target * base^i means x = n*j - i, as the
+    // base is invertible; n*n >= modulus covers every exponent
+    let giant = pow_mod(base, n, modulus);
+    let mut curr = 1 % modulus;
+    for j in 1..=n {
+        curr = mul_mod(curr, giant, modulus);
+        if let Some(&i) = baby.get(&curr) {
+            // n is 2^32 for moduli above (2^32 - 1)^2, and n * j doesn't
+            // always fit a u64 there
+            return u64::try_from(n as u128 * j as u128 - i as u128).ok();
+        }
+    }
+    None
+}
+
+#[test]
+fn test_pow_mod() {
+    assert_eq!(pow_mod(7, 8, 20201227), 5764801);
+    assert_eq!(pow_mod(3, 0, 1), 0);
+    assert_eq!(pow_mod(2, 64, u64::MAX), 1);
+    let m = (1 << 61) - 1;
+    assert_eq!(pow_mod(37, m - 1, m), 1);
+}
+
+#[test]
+fn test_discrete_log() {
+    assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+    assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+    assert_eq!(discrete_log(7, 1, 20201227), Some(0));
+    // Compare with a linear search on small moduli
+    for modulus in 2..60 {
+        for base in 1..modulus {
+            if gcd(base, modulus) != 1 {
+                assert_eq!(discrete_log(base, 2, modulus), None);
+                continue;
+            }
+            for target in 0..modulus {
+                let linear = (0..modulus).find(|&x| pow_mod(base, x, modulus) == target);
+                assert_eq!(discrete_log(base, target, modulus), linear);
+            }
+        }
+    }
+    let m = 1_000_000_007;
+    let x = 987_654_321;
+    let found = discrete_log(5, pow_mod(5, x, m), m).unwrap();
+    assert_eq!(pow_mod(5, found, m), pow_mod(5, x, m));
+}
@@ -0,0 +1,164 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::grid::{Grid, Orientation, Xy};
+use anyhow::{ensure, Result};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+// Pattern //
+
+/// Set of cells that have to be on, drawn as '#' in a picture where every
+/// other character is ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    /// The cells of each distinct orientation, relative to the top-left
+    /// corner of its bounding box
+    orientations: Vec<(Orientation, Vec<Xy>)>,
+}
+
+/// Where a pattern was found, in the image coordinates of the top-left
+/// corner of the transformed pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Match {
+    pub xy: Xy,
+    pub orientation: Orientation,
+}
+
+impl Pattern {
+    pub fn new(width: usize, height: usize, cells: &[Xy]) -> Pattern {
+        let mut seen = BTreeSet::new();
+        let mut orientations = vec![];
+        for o in Orientation::ALL {
+            let mut moved = cells
+                .iter()
+                .map(|&xy| o.apply(xy, width, height))
+                .collect::<Vec<_>>();
+            moved.sort_unstable();
+            // Symmetric patterns look the same in more than one orientation
+            if seen.insert(moved.clone()) {
+                orientations.push((o, moved));
+            }
+        }
+        Pattern {
+            width,
+            height,
+            orientations,
+        }
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.orientations[0].1.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The orientations that give different cells
+    pub fn orientations(&self) -> impl Iterator<Item = Orientation> + '_ {
+        self.orientations.iter().map(|(o, _)| *o)
+    }
+
+    fn cells_of(&self, orientation: Orientation) -> Option<&[Xy]> {
+        self.orientations
+            .iter()
+            .find(|(o, _)| *o == orientation)
+            .map(|(_, cells)| cells.as_slice())
+    }
+
+    /// Image coordinates of the cells of a match
+    pub fn cells_at(&self, m: &Match) -> Vec<Xy> {
+        let (w, h) = (self.width, self.height);
+        let mut cells = self
+            .cells_of(m.orientation)
+            .map(|c| c.to_vec())
+            .unwrap_or_else(|| {
+                let cells = self.orientations[0].1.iter();
+                cells.map(|&xy| m.orientation.apply(xy, w, h)).collect()
+            });
+        for xy in &mut cells {
+            *xy = *xy + m.xy;
+        }
+        cells
+    }
+
+    /// Every place of the image where all cells of some orientation of the
+    /// pattern are on
+    pub fn find<T>(&self, image: &Grid<T>, on: impl Fn(&T) -> bool) -> Vec<Match> {
+        let mut matches = vec![];
+        for (orientation, cells) in &self.orientations {
+            let (w, h) = orientation.size(self.width, self.height);
+            if w > image.width() || h > image.height() {
+                continue;
+            }
+            for y in 0..=image.height() - h {
+                for x in 0..=image.width() - w {
+                    let xy = Xy::new(x as i32, y as i32);
+                    if cells.iter().all(|&c| on(&image[xy + c])) {
+                        matches.push(Match {
+                            xy,
+                            orientation: *orientation,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    /// Copy of the image with the cells of the matches replaced by `mark`
+    pub fn highlight<T: Clone>(&self, image: &Grid<T>, matches: &[Match], mark: T) -> Grid<T> {
+        let mut image = image.clone();
+        for m in matches {
+            for xy in self.cells_at(m) {
+                image[xy] = mark.clone();
+            }
+        }
+        image
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = vec![];
+        let (mut width, mut height) = (0, 0);
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    cells.push(Xy::new(x as i32, y as i32));
+                    width = width.max(x + 1);
+                    height = y + 1;
+                }
+            }
+        }
+        ensure!(!cells.is_empty(), "pattern without '#' cells");
+        Ok(Pattern::new(width, height, &cells))
+    }
+}
+
+#[test]
+fn test_find() -> Result<()> {
+    let pattern = "#.\n##\n".parse::<Pattern>()?;
+    assert_eq!(pattern.len(), 3);
+    assert_eq!(pattern.orientations().count(), 4);
+    let image = "....\n.##.\n.#..\n...#\n".parse::<Grid<char>>()?;
+    let matches = pattern.find(&image, |&c| c == '#');
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].xy, Xy::new(1, 1));
+    assert_eq!(
+        pattern.highlight(&image, &matches, 'O').to_string(),
+        "....\n.OO.\n.O..\n...#\n"
+    );
+    // Trailing spaces and dots are the same
+    let square = "##  \n##\n".parse::<Pattern>()?;
+    assert_eq!((square.width, square.height), (2, 2));
+    assert_eq!(square.orientations().count(), 1);
+    assert!(" . \n".parse::<Pattern>().is_err());
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use std::fmt;
+use std::io::BufRead;
+
+/// A day's puzzle: the input is parsed once into `Input`, and both parts
+/// work on it.
+pub trait Solver {
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(bufin: impl BufRead) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    fn run1(bufin: impl BufRead) -> Result<Self::Answer1> {
+        Self::part1(&Self::parse(bufin)?)
+    }
+
+    fn run2(bufin: impl BufRead) -> Result<Self::Answer2> {
+        Self::part2(&Self::parse(bufin)?)
+    }
+}
@@ -0,0 +1,524 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use crate::ParseError;
+use anyhow::{anyhow, ensure, Result};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+// Instr //
+
+/// An instruction with an opcode name and a signed argument, like "acc +1"
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Instr {
+    pub op: String,
+    pub arg: i32,
+}
+
+impl Instr {
+    pub fn new(op: &str, arg: i32) -> Instr {
+        Instr {
+            op: op.to_string(),
+            arg,
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:+}", self.op, self.arg)
+    }
+}
+
+/// Parses the syntax only; opcodes are checked against an OpTable when
+/// the program is loaded
+impl FromStr for Instr {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (opstr, argstr) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(0, 1, s.chars().count() + 1, s, "an argument"))?;
+        if opstr.is_empty() || !opstr.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(ParseError::new(0, 1, 1, s, "an opcode"));
+        }
+        let arg = argstr
+            .parse::<i32>()
+            .map_err(|_| ParseError::new(0, 1, opstr.len() + 2, s, "a signed number"))?;
+        Ok(Instr::new(opstr, arg))
+    }
+}
+
+// OpTable //
+
+/// Registers of the machine
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cpu {
+    pub acc: i32,
+    pub pc: i32,
+}
+
+/// Executes an instruction given its argument; it must also update pc, and
+/// return None without changing the registers if one of them would overflow
+pub type Handler = fn(&mut Cpu, i32) -> Option<()>;
+
+/// The known opcodes, indexed by name
+#[derive(Clone)]
+pub struct OpTable {
+    ops: BTreeMap<String, Handler>,
+}
+
+impl OpTable {
+    pub fn empty() -> OpTable {
+        OpTable {
+            ops: BTreeMap::new(),
+        }
+    }
+
+    /// Add or replace an opcode
+    pub fn insert(&mut self, name: &str, handler: Handler) -> &mut OpTable {
+        self.ops.insert(name.to_string(), handler);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<Handler> {
+        self.ops.get(name).copied()
+    }
+
+    /// The opcode names as a list for error messages: "a, b or c"
+    pub fn describe(&self) -> String {
+        let names = self.ops.keys().map(|s| s.as_str()).collect::<Vec<_>>();
+        match names.split_last() {
+            None => "nothing".to_string(),
+            Some((last, [])) => last.to_string(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        }
+    }
+
+    /// Check that all instructions use known opcodes, pointing the error
+    /// at the first line that doesn't
+    pub fn check(&self, day: u32, program: &[Instr]) -> Result<(), ParseError> {
+        for (i, instr) in program.iter().enumerate() {
+            if self.get(&instr.op).is_none() {
+                return Err(ParseError::new(
+                    day,
+                    i + 1,
+                    1,
+                    &instr.to_string(),
+                    &self.describe(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The opcodes of day 8: acc, jmp and nop
+impl Default for OpTable {
+    fn default() -> OpTable {
+        let mut table = OpTable::empty();
+        table
+            .insert("acc", |cpu, arg| {
+                cpu.acc = cpu.acc.checked_add(arg)?;
+                cpu.pc += 1;
+                Some(())
+            })
+            .insert("jmp", |cpu, arg| {
+                cpu.pc = cpu.pc.checked_add(arg)?;
+                Some(())
+            })
+            .insert("nop", |cpu, _| {
+                cpu.pc += 1;
+                Some(())
+            });
+        table
+    }
+}
+
+/// Parse a program, one instruction per line, checking the opcodes
+pub fn parse_program(day: u32, bufin: impl BufRead, table: &OpTable) -> Result<Vec<Instr>> {
+    let mut program = vec![];
+    for (i, line_opt) in bufin.lines().enumerate() {
+        let line = line_opt?;
+        let instr = line.parse::<Instr>().map_err(|e| ParseError {
+            day,
+            ..e.at_line(i + 1)
+        })?;
+        if table.get(&instr.op).is_none() {
+            return Err(ParseError::new(day, i + 1, 1, &line, &table.describe()).into());
+        }
+        program.push(instr);
+    }
+    Ok(program)
+}
+
+// Vm //
+
+/// Why the execution stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// pc is right after the last instruction
+    Halt,
+    /// The instruction at pc was already executed
+    Loop(i32),
+    /// pc is outside the program and not at its end
+    OutOfBounds(i32),
+    /// pc is at a breakpoint
+    Break(i32),
+    /// The instruction at pc would overflow a register
+    Overflow(i32),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Halt => write!(f, "halted"),
+            Outcome::Loop(pc) => write!(f, "loop at {}", pc),
+            Outcome::OutOfBounds(pc) => write!(f, "pc {} out of bounds", pc),
+            Outcome::Break(pc) => write!(f, "breakpoint at {}", pc),
+            Outcome::Overflow(pc) => write!(f, "overflow at {}", pc),
+        }
+    }
+}
+
+/// An executed instruction, with the registers before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub cpu: Cpu,
+    pub instr: Instr,
+}
+
+pub struct Vm {
+    pub cpu: Cpu,
+    pub program: Vec<Instr>,
+    pub breakpoints: BTreeSet<i32>,
+    /// Executed instructions, if tracing is enabled
+    pub trace: Option<Vec<TraceEntry>>,
+    table: OpTable,
+    code: Vec<Handler>,
+    executed: BTreeSet<i32>,
+    /// pc of the instruction that overflowed, which stops the execution
+    overflow: Option<i32>,
+}
+
+impl Vm {
+    pub fn new(table: OpTable, program: Vec<Instr>) -> Result<Vm> {
+        table.check(0, &program)?;
+        let code = program
+            .iter()
+            .map(|instr| table.get(&instr.op).unwrap())
+            .collect();
+        Ok(Vm {
+            cpu: Cpu::default(),
+            program,
+            breakpoints: BTreeSet::new(),
+            trace: None,
+            table,
+            code,
+            executed: BTreeSet::new(),
+            overflow: None,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.cpu = Cpu::default();
+        self.executed.clear();
+        self.overflow = None;
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    /// Replace an instruction; the opcode must be in the table
+    pub fn patch(&mut self, index: usize, instr: Instr) -> Result<()> {
+        let handler = self
+            .table
+            .get(&instr.op)
+            .ok_or_else(|| anyhow!("unknown opcode {}", instr.op))?;
+        ensure!(
+            index < self.program.len(),
+            "instruction {} out of range, the program has {}",
+            index,
+            self.program.len()
+        );
+        self.code[index] = handler;
+        self.program[index] = instr;
+        Ok(())
+    }
+
+    /// Where the execution would stop, if it would, before running the
+    /// next instruction
+    fn check(&self) -> Option<Outcome> {
+        let pc = self.cpu.pc;
+        if let Some(pc) = self.overflow {
+            Some(Outcome::Overflow(pc))
+        } else if pc == self.program.len() as i32 {
+            Some(Outcome::Halt)
+        } else if pc < 0 || pc > self.program.len() as i32 {
+            Some(Outcome::OutOfBounds(pc))
+        } else if self.executed.contains(&pc) {
+            Some(Outcome::Loop(pc))
+        } else {
+            None
+        }
+    }
+
+    /// Execute a single instruction, ignoring breakpoints
+    pub fn step(&mut self) -> Option<Outcome> {
+        if let Some(outcome) = self.check() {
+            return Some(outcome);
+        }
+        let pc = self.cpu.pc;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                cpu: self.cpu,
+                instr: self.program[pc as usize].clone(),
+            });
+        }
+        self.executed.insert(pc);
+        if (self.code[pc as usize])(&mut self.cpu, self.program[pc as usize].arg).is_none() {
+            self.overflow = Some(pc);
+            return Some(Outcome::Overflow(pc));
+        }
+        None
+    }
+
+    /// Run until the program stops or reaches a breakpoint; the
+    /// instruction at pc is always executed, so that we can continue from
+    /// a breakpoint
+    pub fn run(&mut self) -> Outcome {
+        if let Some(outcome) = self.step() {
+            return outcome;
+        }
+        loop {
+            if let Some(outcome) = self.check() {
+                return outcome;
+            }
+            if self.breakpoints.contains(&self.cpu.pc) {
+                return Outcome::Break(self.cpu.pc);
+            }
+            self.step();
+        }
+    }
+
+    /// Run from the start, ignoring breakpoints
+    pub fn run_all(&mut self) -> Outcome {
+        self.reset();
+        loop {
+            if let Some(outcome) = self.step() {
+                return outcome;
+            }
+        }
+    }
+}
+
+// Repl //
+
+const HELP: &str = "commands:
+  s, step [n]     execute n instructions (default 1)
+  c, continue     run until the program stops or hits a breakpoint
+  b, break <pc>   add a breakpoint
+  d, delete <pc>  remove a breakpoint
+  p, print        show the registers and the next instruction
+  l, list         show the program
+  t, trace        show the executed instructions
+  r, reset        go back to the start
+  h, help
+  q, quit";
+
+fn show_state(vm: &Vm, out: &mut impl Write) -> Result<()> {
+    let next = vm
+        .program
+        .get(vm.cpu.pc as usize)
+        .map_or_else(|| "-".to_string(), |i| i.to_string());
+    writeln!(out, "pc {} acc {} next {}", vm.cpu.pc, vm.cpu.acc, next)?;
+    Ok(())
+}
+
+fn repl_cmd(vm: &mut Vm, line: &str, out: &mut impl Write) -> Result<bool> {
+    let mut words = line.split_whitespace();
+    let cmd = match words.next() {
+        None => return Ok(true),
+        Some(cmd) => cmd,
+    };
+    let mut num = || -> Result<Option<i32>> {
+        words
+            .next()
+            .map(|w| {
+                w.parse::<i32>()
+                    .map_err(|_| anyhow!("invalid number {}", w))
+            })
+            .transpose()
+    };
+    match cmd {
+        "s" | "step" => {
+            for _ in 0..num()?.unwrap_or(1) {
+                if let Some(outcome) = vm.step() {
+                    writeln!(out, "{}", outcome)?;
+                    break;
+                }
+            }
+            show_state(vm, out)?;
+        }
+        "c" | "continue" => {
+            let outcome = vm.run();
+            writeln!(out, "{}", outcome)?;
+            show_state(vm, out)?;
+        }
+        "b" | "break" => {
+            let pc = num()?.ok_or_else(|| anyhow!("break requires a pc"))?;
+            vm.breakpoints.insert(pc);
+        }
+        "d" | "delete" => {
+            let pc = num()?.ok_or_else(|| anyhow!("delete requires a pc"))?;
+            if !vm.breakpoints.remove(&pc) {
+                writeln!(out, "no breakpoint at {}", pc)?;
+            }
+        }
+        "p" | "print" => show_state(vm, out)?,
+        "l" | "list" => {
+            for (i, instr) in vm.program.iter().enumerate() {
+                let mark = if vm.cpu.pc == i as i32 { '>' } else { ' ' };
+                let bp = if vm.breakpoints.contains(&(i as i32)) {
+                    '*'
+                } else {
+                    ' '
+                };
+                writeln!(out, "{}{}{:4} {}", mark, bp, i, instr)?;
+            }
+        }
+        "t" | "trace" => {
+            for entry in vm.trace.iter().flatten() {
+                writeln!(
+                    out,
+                    "{:4} {:<10} acc {}",
+                    entry.cpu.pc,
+                    entry.instr.to_string(),
+                    entry.cpu.acc
+                )?;
+            }
+        }
+        "r" | "reset" => {
+            vm.reset();
+            show_state(vm, out)?;
+        }
+        "q" | "quit" => return Ok(false),
+        "h" | "help" => writeln!(out, "{}", HELP)?,
+        _ => writeln!(out, "unknown command {}, try help", cmd)?,
+    }
+    Ok(true)
+}
+
+/// Debug the program interactively, reading commands from bufin
+pub fn repl(vm: &mut Vm, bufin: impl BufRead, mut out: impl Write) -> Result<()> {
+    if vm.trace.is_none() {
+        vm.trace = Some(vec![]);
+    }
+    show_state(vm, &mut out)?;
+    write!(out, "> ")?;
+    out.flush()?;
+    for line_opt in bufin.lines() {
+        match repl_cmd(vm, &line_opt?, &mut out) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => writeln!(out, "error: {}", e)?,
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+const EXAMPLE: &[u8] = b"nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+#[test]
+fn test_outcomes() -> Result<()> {
+    let table = OpTable::default();
+    let mut vm = Vm::new(table.clone(), parse_program(8, EXAMPLE, &table)?)?;
+    assert_eq!(vm.run_all(), Outcome::Loop(1));
+    assert_eq!(vm.cpu.acc, 5);
+    vm.patch(7, Instr::new("nop", -4))?;
+    assert_eq!(vm.run_all(), Outcome::Halt);
+    assert_eq!(vm.cpu.acc, 8);
+    vm.patch(8, Instr::new("jmp", 5))?;
+    assert_eq!(vm.run_all(), Outcome::OutOfBounds(13));
+    assert!(vm.patch(0, Instr::new("xyz", 0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_breakpoints_trace() -> Result<()> {
+    let table = OpTable::default();
+    let mut vm = Vm::new(table.clone(), parse_program(8, EXAMPLE, &table)?)?;
+    vm.trace = Some(vec![]);
+    vm.breakpoints.insert(3);
+    assert_eq!(vm.run(), Outcome::Break(3));
+    assert_eq!(vm.cpu.acc, 2);
+    assert_eq!(vm.run(), Outcome::Loop(1));
+    let pcs = vm
+        .trace
+        .iter()
+        .flatten()
+        .map(|e| e.cpu.pc)
+        .collect::<Vec<_>>();
+    assert_eq!(pcs, vec![0, 1, 2, 6, 7, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_extend() -> Result<()> {
+    let mut table = OpTable::default();
+    table.insert("mul", |cpu, arg| {
+        cpu.acc = cpu.acc.checked_mul(arg)?;
+        cpu.pc += 1;
+        Some(())
+    });
+    assert_eq!(table.describe(), "acc, jmp, mul or nop");
+    let program = parse_program(0, &b"acc +3\nmul +7\n"[..], &table)?;
+    let mut vm = Vm::new(table, program)?;
+    assert_eq!(vm.run_all(), Outcome::Halt);
+    assert_eq!(vm.cpu.acc, 21);
+    Ok(())
+}
+
+#[test]
+fn test_overflow() -> Result<()> {
+    let table = OpTable::default();
+    let program = parse_program(0, &b"acc +2147483647\nacc +1\nnop +0\n"[..], &table)?;
+    let mut vm = Vm::new(table.clone(), program)?;
+    assert_eq!(vm.run_all(), Outcome::Overflow(1));
+    assert_eq!(
+        vm.cpu,
+        Cpu {
+            acc: i32::MAX,
+            pc: 1
+        }
+    );
+    // The overflow stops the execution until a reset
+    assert_eq!(vm.run(), Outcome::Overflow(1));
+    vm.patch(1, Instr::new("jmp", i32::MAX))?;
+    assert_eq!(vm.run_all(), Outcome::Overflow(1));
+    vm.patch(1, Instr::new("acc", -1))?;
+    assert_eq!(vm.run_all(), Outcome::Halt);
+    assert!(vm.patch(3, Instr::new("nop", 0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_repl() -> Result<()> {
+    let table = OpTable::default();
+    let mut vm = Vm::new(table.clone(), parse_program(8, EXAMPLE, &table)?)?;
+    let mut out = vec![];
+    repl(&mut vm, &b"b 6\nc\ns 2\nt\nxyz\nq\np\n"[..], &mut out)?;
+    let out = String::from_utf8(out)?;
+    assert!(out.contains("breakpoint at 6\npc 6 acc 1 next acc +1\n"));
+    assert!(out.contains("pc 3 acc 2 next acc +3\n"));
+    assert!(out.contains("   6 acc +1     acc 1\n"));
+    assert!(out.contains("unknown command xyz"));
+    assert_eq!(vm.cpu.pc, 3);
+    Ok(())
+}
@@ -1,2 +1,22 @@
+// Copyright (C) 2020 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use adventofcode2020::*;
+use anyhow::Result;
+
 #[test]
-fn test() {}
+fn test_day01() -> Result<()> {
+    let input: &[u8] = b"1721\n979\n366\n299\n675\n1456\n";
+    assert_eq!(day01::solve_a(input)?, 514579);
+    assert_eq!(day01::solve_b(input)?, 241861950);
+    Ok(())
+}
+
+#[test]
+fn test_day08() -> Result<()> {
+    let input: &[u8] = b"nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+    assert_eq!(day08::solve_a(input)?, 5);
+    assert_eq!(day08::solve_b(input)?, 8);
+    Ok(())
+}